@@ -1,8 +1,98 @@
-use crate::Decode;
+use crate::{Decode, ProtoError, Tag, WIRE_TYPE_END_GROUP};
+
+/// limits protecting against untrusted input. sizes are not limited by default, nesting of
+/// messages and groups is limited to [DecodeOptions::DEFAULT_MAX_DEPTH] levels
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DecodeOptions {
+    max_message_size: usize,
+    max_length: usize,
+    max_repeated: usize,
+    max_depth: usize,
+}
+
+impl Default for DecodeOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DecodeOptions {
+    /// nesting limit of the default options, the same as used by the C++ and Java implementations
+    pub const DEFAULT_MAX_DEPTH: usize = 100;
+
+    pub const fn new() -> Self {
+        Self {
+            max_message_size: usize::MAX,
+            max_length: usize::MAX,
+            max_repeated: usize::MAX,
+            max_depth: Self::DEFAULT_MAX_DEPTH,
+        }
+    }
+
+    /// maximum size of the encoded message in bytes
+    pub const fn max_message_size(mut self, size: usize) -> Self {
+        self.max_message_size = size;
+        self
+    }
+
+    /// maximum length of a single `bytes` or `string` value in bytes
+    pub const fn max_length(mut self, length: usize) -> Self {
+        self.max_length = length;
+        self
+    }
+
+    /// maximum number of elements of a single repeated or map field
+    pub const fn max_repeated(mut self, count: usize) -> Self {
+        self.max_repeated = count;
+        self
+    }
+
+    /// maximum number of messages and groups nested in each other, the outermost message is not
+    /// counted. nesting is decoded recursively, so this bounds the stack usage
+    pub const fn max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = depth;
+        self
+    }
+
+    #[inline]
+    pub fn check_message_size(&self, size: usize) -> Result<(), ProtoError> {
+        if size > self.max_message_size {
+            return Err(ProtoError::MessageTooLarge(self.max_message_size));
+        }
+        Ok(())
+    }
+
+    #[inline]
+    pub fn check_length(&self, length: usize) -> Result<(), ProtoError> {
+        if length > self.max_length {
+            return Err(ProtoError::LengthTooLarge(self.max_length));
+        }
+        Ok(())
+    }
+
+    /// checks if another element can be added to a field holding `count` elements
+    #[inline]
+    pub fn check_repeated(&self, count: usize) -> Result<(), ProtoError> {
+        if count >= self.max_repeated {
+            return Err(ProtoError::TooManyElements(self.max_repeated));
+        }
+        Ok(())
+    }
+
+    #[inline]
+    pub fn check_depth(&self, depth: usize) -> Result<(), ProtoError> {
+        if depth > self.max_depth {
+            return Err(ProtoError::RecursionLimitExceeded(self.max_depth));
+        }
+        Ok(())
+    }
+}
 
 pub struct Decoder<'buf> {
     buffer: &'buf [u8],
     position: usize,
+    options: DecodeOptions,
+    depth: usize,
 }
 
 impl<'buf> Decoder<'buf> {
@@ -11,7 +101,31 @@ impl<'buf> Decoder<'buf> {
         Self {
             buffer,
             position: 0,
+            options: DecodeOptions::new(),
+            depth: 0,
+        }
+    }
+
+    /// a decoder enforcing the given limits, fails if the buffer exceeds the maximum message size
+    pub fn with_options(buffer: &'buf [u8], options: DecodeOptions) -> Result<Self, ProtoError> {
+        options.check_message_size(buffer.len())?;
+        Ok(Self {
+            buffer,
+            position: 0,
+            options,
+            depth: 0,
+        })
+    }
+
+    /// reads the length of a length encoded value and checks it against buffer and limits
+    #[inline]
+    fn decode_length(&mut self) -> Result<usize, ProtoError> {
+        let len = self.decode_uint32()? as usize;
+        self.options.check_length(len)?;
+        if self.buffer().len() < len {
+            return Err(ProtoError::UnexpectedEof);
         }
+        Ok(len)
     }
 
     #[inline]
@@ -52,10 +166,33 @@ impl<'buf> Decode for Decoder<'buf> {
     }
 
     #[inline]
-    fn sub_decoder(&mut self, size: usize) -> impl Decode {
-        let sub_buffer = &self.buffer()[0..size];
+    fn options(&self) -> DecodeOptions {
+        self.options
+    }
+
+    #[inline]
+    fn depth(&self) -> usize {
+        self.depth
+    }
+
+    #[inline]
+    fn set_depth(&mut self, depth: usize) {
+        self.depth = depth;
+    }
+
+    #[inline]
+    fn sub_decoder(&mut self, size: usize) -> Result<impl Decode, ProtoError> {
+        let sub_buffer = self
+            .buffer()
+            .get(0..size)
+            .ok_or(ProtoError::UnexpectedEof)?;
         self.advance(size);
-        Decoder::new(sub_buffer)
+        Ok(Decoder {
+            buffer: sub_buffer,
+            position: 0,
+            options: self.options,
+            depth: self.depth,
+        })
     }
 
     #[inline]
@@ -135,7 +272,7 @@ impl<'buf> Decode for Decoder<'buf> {
 
     #[inline]
     fn decode_bytes(&mut self) -> Result<Vec<u8>, crate::error::ProtoError> {
-        let len = self.decode_uint32()? as usize;
+        let len = self.decode_length()?;
         let vec = (self.buffer()[0..len]).to_vec();
         self.advance(len);
         Ok(vec)
@@ -143,9 +280,470 @@ impl<'buf> Decode for Decoder<'buf> {
 
     #[inline]
     fn decode_string(&mut self) -> Result<String, crate::ProtoError> {
-        let len = self.decode_uint32()? as usize;
+        let len = self.decode_length()?;
         let str = str::from_utf8(&self.buffer()[0..len])?;
         self.advance(len);
         Ok(str.into())
     }
 }
+
+/// a [Decode] implementation backed by [bytes::Bytes]. decoding `bytes` fields into [bytes::Bytes]
+/// slices the underlying buffer instead of copying it
+#[cfg(feature = "bytes")]
+pub struct BytesDecoder {
+    buffer: bytes::Bytes,
+    position: usize,
+    options: DecodeOptions,
+    depth: usize,
+}
+
+#[cfg(feature = "bytes")]
+impl BytesDecoder {
+    #[inline]
+    pub fn new(buffer: bytes::Bytes) -> Self {
+        Self {
+            buffer,
+            position: 0,
+            options: DecodeOptions::new(),
+            depth: 0,
+        }
+    }
+
+    /// a decoder enforcing the given limits, fails if the buffer exceeds the maximum message size
+    pub fn with_options(buffer: bytes::Bytes, options: DecodeOptions) -> Result<Self, ProtoError> {
+        options.check_message_size(buffer.len())?;
+        Ok(Self {
+            buffer,
+            position: 0,
+            options,
+            depth: 0,
+        })
+    }
+
+    #[inline]
+    fn with_slice<T>(
+        &mut self,
+        f: impl FnOnce(&mut Decoder<'_>) -> Result<T, crate::ProtoError>,
+    ) -> Result<T, crate::ProtoError> {
+        let mut decoder = Decoder {
+            buffer: &self.buffer[self.position..],
+            position: 0,
+            options: self.options,
+            depth: self.depth,
+        };
+        let value = f(&mut decoder)?;
+        self.position += decoder.position();
+        Ok(value)
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl Decode for BytesDecoder {
+    #[inline]
+    fn eof(&self) -> bool {
+        self.position >= self.buffer.len()
+    }
+
+    #[inline]
+    fn position(&self) -> usize {
+        self.position
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    #[inline]
+    fn advance(&mut self, size: usize) {
+        self.position += size;
+    }
+
+    #[inline]
+    fn options(&self) -> DecodeOptions {
+        self.options
+    }
+
+    #[inline]
+    fn depth(&self) -> usize {
+        self.depth
+    }
+
+    #[inline]
+    fn set_depth(&mut self, depth: usize) {
+        self.depth = depth;
+    }
+
+    #[inline]
+    fn sub_decoder(&mut self, size: usize) -> Result<impl Decode, ProtoError> {
+        if self.buffer().len() < size {
+            return Err(ProtoError::UnexpectedEof);
+        }
+        let sub_buffer = self.buffer.slice(self.position..self.position + size);
+        self.advance(size);
+        Ok(BytesDecoder {
+            buffer: sub_buffer,
+            position: 0,
+            options: self.options,
+            depth: self.depth,
+        })
+    }
+
+    #[inline]
+    fn buffer(&self) -> &[u8] {
+        &self.buffer[self.position..]
+    }
+
+    #[inline]
+    fn decode_sint32(&mut self) -> Result<i32, crate::error::ProtoError> {
+        self.with_slice(|decoder| decoder.decode_sint32())
+    }
+
+    #[inline]
+    fn decode_sint64(&mut self) -> Result<i64, crate::error::ProtoError> {
+        self.with_slice(|decoder| decoder.decode_sint64())
+    }
+
+    #[inline]
+    fn decode_uint32(&mut self) -> Result<u32, crate::error::ProtoError> {
+        self.with_slice(|decoder| decoder.decode_uint32())
+    }
+
+    #[inline]
+    fn decode_uint64(&mut self) -> Result<u64, crate::error::ProtoError> {
+        self.with_slice(|decoder| decoder.decode_uint64())
+    }
+
+    #[inline]
+    fn decode_sfixed32(&mut self) -> Result<i32, crate::error::ProtoError> {
+        self.with_slice(|decoder| decoder.decode_sfixed32())
+    }
+
+    #[inline]
+    fn decode_sfixed64(&mut self) -> Result<i64, crate::error::ProtoError> {
+        self.with_slice(|decoder| decoder.decode_sfixed64())
+    }
+
+    #[inline]
+    fn decode_fixed32(&mut self) -> Result<u32, crate::error::ProtoError> {
+        self.with_slice(|decoder| decoder.decode_fixed32())
+    }
+
+    #[inline]
+    fn decode_fixed64(&mut self) -> Result<u64, crate::error::ProtoError> {
+        self.with_slice(|decoder| decoder.decode_fixed64())
+    }
+
+    #[inline]
+    fn decode_float(&mut self) -> Result<f32, crate::error::ProtoError> {
+        self.with_slice(|decoder| decoder.decode_float())
+    }
+
+    #[inline]
+    fn decode_double(&mut self) -> Result<f64, crate::error::ProtoError> {
+        self.with_slice(|decoder| decoder.decode_double())
+    }
+
+    #[inline]
+    fn decode_bytes(&mut self) -> Result<Vec<u8>, crate::error::ProtoError> {
+        self.with_slice(|decoder| decoder.decode_bytes())
+    }
+
+    #[inline]
+    fn decode_string(&mut self) -> Result<String, crate::ProtoError> {
+        self.with_slice(|decoder| decoder.decode_string())
+    }
+
+    #[inline]
+    fn decode_shared_bytes(&mut self) -> Result<bytes::Bytes, crate::ProtoError> {
+        let len = self.with_slice(|decoder| decoder.decode_length())?;
+        let bytes = self.buffer.slice(self.position..self.position + len);
+        self.advance(len);
+        Ok(bytes)
+    }
+}
+
+/// decodes the fields of a group as a message: reports the end of the buffer at the end group tag
+/// of the group, so the fields are decoded in a single pass without knowing the size of the group
+pub(crate) struct GroupDecoder<'a, D> {
+    decoder: &'a mut D,
+    end_tag: [u8; 5],
+    end_tag_len: usize,
+}
+
+impl<'a, D: Decode> GroupDecoder<'a, D> {
+    pub(crate) fn new(decoder: &'a mut D, field_number: u32) -> Self {
+        let mut value = u32::from(Tag::from_parts(field_number, WIRE_TYPE_END_GROUP));
+        let mut end_tag = [0u8; 5];
+        let mut end_tag_len = 0;
+        loop {
+            #[allow(clippy::cast_possible_truncation)]
+            let byte = (value & 0x7f) as u8;
+            value >>= 7;
+            if value == 0 {
+                end_tag[end_tag_len] = byte;
+                end_tag_len += 1;
+                break;
+            }
+            end_tag[end_tag_len] = byte | 0x80;
+            end_tag_len += 1;
+        }
+
+        Self {
+            decoder,
+            end_tag,
+            end_tag_len,
+        }
+    }
+
+    /// consumes the end group tag, fails if the buffer or an enclosing group ended before it
+    pub(crate) fn finish(self) -> Result<(), ProtoError> {
+        if !self
+            .decoder
+            .buffer()
+            .starts_with(&self.end_tag[..self.end_tag_len])
+        {
+            return Err(ProtoError::UnexpectedEof);
+        }
+        self.decoder.advance(self.end_tag_len);
+        Ok(())
+    }
+}
+
+impl<D: Decode> Decode for GroupDecoder<'_, D> {
+    #[inline]
+    fn eof(&self) -> bool {
+        self.decoder.eof()
+            || self
+                .decoder
+                .buffer()
+                .starts_with(&self.end_tag[..self.end_tag_len])
+    }
+
+    #[inline]
+    fn position(&self) -> usize {
+        self.decoder.position()
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.decoder.len()
+    }
+
+    #[inline]
+    fn advance(&mut self, size: usize) {
+        self.decoder.advance(size);
+    }
+
+    #[inline]
+    fn options(&self) -> DecodeOptions {
+        self.decoder.options()
+    }
+
+    #[inline]
+    fn depth(&self) -> usize {
+        self.decoder.depth()
+    }
+
+    #[inline]
+    fn set_depth(&mut self, depth: usize) {
+        self.decoder.set_depth(depth);
+    }
+
+    #[inline]
+    fn sub_decoder(&mut self, size: usize) -> Result<impl Decode, ProtoError> {
+        self.decoder.sub_decoder(size)
+    }
+
+    #[inline]
+    fn buffer(&self) -> &[u8] {
+        self.decoder.buffer()
+    }
+
+    #[inline]
+    fn decode_int32(&mut self) -> Result<i32, ProtoError> {
+        self.decoder.decode_int32()
+    }
+
+    #[inline]
+    fn decode_int64(&mut self) -> Result<i64, ProtoError> {
+        self.decoder.decode_int64()
+    }
+
+    #[inline]
+    fn decode_sint32(&mut self) -> Result<i32, ProtoError> {
+        self.decoder.decode_sint32()
+    }
+
+    #[inline]
+    fn decode_sint64(&mut self) -> Result<i64, ProtoError> {
+        self.decoder.decode_sint64()
+    }
+
+    #[inline]
+    fn decode_uint32(&mut self) -> Result<u32, ProtoError> {
+        self.decoder.decode_uint32()
+    }
+
+    #[inline]
+    fn decode_uint64(&mut self) -> Result<u64, ProtoError> {
+        self.decoder.decode_uint64()
+    }
+
+    #[inline]
+    fn decode_sfixed32(&mut self) -> Result<i32, ProtoError> {
+        self.decoder.decode_sfixed32()
+    }
+
+    #[inline]
+    fn decode_sfixed64(&mut self) -> Result<i64, ProtoError> {
+        self.decoder.decode_sfixed64()
+    }
+
+    #[inline]
+    fn decode_fixed32(&mut self) -> Result<u32, ProtoError> {
+        self.decoder.decode_fixed32()
+    }
+
+    #[inline]
+    fn decode_fixed64(&mut self) -> Result<u64, ProtoError> {
+        self.decoder.decode_fixed64()
+    }
+
+    #[inline]
+    fn decode_float(&mut self) -> Result<f32, ProtoError> {
+        self.decoder.decode_float()
+    }
+
+    #[inline]
+    fn decode_double(&mut self) -> Result<f64, ProtoError> {
+        self.decoder.decode_double()
+    }
+
+    #[inline]
+    fn decode_bytes(&mut self) -> Result<Vec<u8>, ProtoError> {
+        self.decoder.decode_bytes()
+    }
+
+    #[inline]
+    fn decode_string(&mut self) -> Result<String, ProtoError> {
+        self.decoder.decode_string()
+    }
+
+    #[inline]
+    fn decode_string_lossy(&mut self) -> Result<String, ProtoError> {
+        self.decoder.decode_string_lossy()
+    }
+
+    #[cfg(feature = "bytes")]
+    #[inline]
+    fn decode_shared_bytes(&mut self) -> Result<bytes::Bytes, ProtoError> {
+        self.decoder.decode_shared_bytes()
+    }
+
+    #[inline]
+    fn decode_bool(&mut self) -> Result<bool, ProtoError> {
+        self.decoder.decode_bool()
+    }
+
+    #[inline]
+    fn decode_tag(&mut self) -> Result<Tag, ProtoError> {
+        self.decoder.decode_tag()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Decode, Packed, ProtoError, scalars::UInt32};
+
+    use super::{DecodeOptions, Decoder};
+
+    #[test]
+    fn limits() {
+        let options = DecodeOptions::new().max_message_size(4);
+        assert!(Decoder::with_options(b"\x03gin", options).is_ok());
+        assert!(matches!(
+            Decoder::with_options(b"\x05tonic", options),
+            Err(ProtoError::MessageTooLarge(4))
+        ));
+
+        let options = DecodeOptions::new().max_length(3);
+        let mut decoder = Decoder::with_options(b"\x03gin\x05tonic", options).unwrap();
+        assert_eq!(decoder.decode_string().unwrap(), "gin");
+        assert!(matches!(
+            decoder.decode_bytes(),
+            Err(ProtoError::LengthTooLarge(3))
+        ));
+
+        // nested decoders keep the limits
+        let options = DecodeOptions::new().max_repeated(2);
+        let mut decoder = Decoder::with_options(b"\x04\x03\x01\x02\x03", options).unwrap();
+        let mut values = Vec::<u32>::new();
+        let mut nested = decoder.sub_decoder(5).unwrap();
+        assert!(matches!(
+            <Vec<u32> as Packed<UInt32>>::decode(&mut nested, &mut values),
+            Err(ProtoError::TooManyElements(2))
+        ));
+    }
+
+    #[test]
+    fn length_exceeds_buffer() {
+        let mut decoder = Decoder::new(b"\x05gin");
+        assert!(matches!(
+            decoder.decode_string(),
+            Err(ProtoError::UnexpectedEof)
+        ));
+    }
+
+    #[test]
+    fn sub_decoder_exceeds_buffer() {
+        let mut decoder = Decoder::new(b"gin");
+        assert!(matches!(
+            decoder.sub_decoder(4),
+            Err(ProtoError::UnexpectedEof)
+        ));
+    }
+
+    #[test]
+    fn depth_limit() {
+        fn skip(depth: usize, options: DecodeOptions) -> Result<(), ProtoError> {
+            // unknown groups of field 1 nested in each other
+            let mut buffer = vec![0x0b; depth];
+            buffer.extend(vec![0x0c; depth]);
+            let mut decoder = Decoder::with_options(&buffer, options).unwrap();
+            let tag = decoder.decode_tag().unwrap();
+            decoder.skip_field(tag)?;
+            assert!(decoder.eof());
+            assert_eq!(decoder.depth(), 0);
+            Ok(())
+        }
+
+        assert!(skip(100, DecodeOptions::new()).is_ok());
+        assert!(matches!(
+            skip(101, DecodeOptions::new()),
+            Err(ProtoError::RecursionLimitExceeded(100))
+        ));
+        // deep enough to overflow the stack without a limit
+        assert!(matches!(
+            skip(200_000, DecodeOptions::new()),
+            Err(ProtoError::RecursionLimitExceeded(100))
+        ));
+
+        assert!(skip(2, DecodeOptions::new().max_depth(2)).is_ok());
+        assert!(matches!(
+            skip(3, DecodeOptions::new().max_depth(2)),
+            Err(ProtoError::RecursionLimitExceeded(2))
+        ));
+    }
+
+    #[cfg(feature = "bytes")]
+    #[test]
+    fn bytes_sub_decoder_exceeds_buffer() {
+        let mut decoder = super::BytesDecoder::new(bytes::Bytes::from_static(b"gin"));
+        assert!(matches!(
+            decoder.sub_decoder(4),
+            Err(ProtoError::UnexpectedEof)
+        ));
+        assert!(decoder.sub_decoder(3).is_ok());
+    }
+}
@@ -0,0 +1,291 @@
+//! streams of length delimited messages, each message is prefixed by its size encoded as varint
+
+use std::{io::Read, marker::PhantomData};
+
+use varint_simd::VarIntDecodeError;
+
+use crate::{
+    Message,
+    decoder::{DecodeOptions, Decoder},
+    error::ProtoError,
+};
+
+/// maximum size of a varint encoded `u64`
+const MAX_VARINT_SIZE: usize = 10;
+
+/// iterates over length delimited messages read from an [std::io::Read]
+pub struct DelimitedReader<R, T> {
+    reader: R,
+    buffer: Vec<u8>,
+    options: DecodeOptions,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<R: Read, T: Message> DelimitedReader<R, T> {
+    pub fn new(reader: R) -> Self {
+        Self::with_options(reader, DecodeOptions::new())
+    }
+
+    /// a reader enforcing the given limits, messages exceeding the maximum message size are
+    /// rejected before they are read
+    pub fn with_options(reader: R, options: DecodeOptions) -> Self {
+        Self {
+            reader,
+            buffer: Vec::new(),
+            options,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// reads the size prefix, `None` if the reader ended before the next message
+    fn read_size(&mut self) -> Result<Option<usize>, ProtoError> {
+        let mut size = 0u64;
+
+        for index in 0..MAX_VARINT_SIZE {
+            let mut byte = [0u8];
+            if self.reader.read(&mut byte)? == 0 {
+                return if index == 0 {
+                    Ok(None)
+                } else {
+                    Err(ProtoError::UnexpectedEof)
+                };
+            }
+
+            size |= u64::from(byte[0] & 0x7f) << (7 * index);
+            if byte[0] & 0x80 == 0 {
+                return usize::try_from(size)
+                    .map(Some)
+                    .map_err(|_| VarIntDecodeError::Overflow.into());
+            }
+        }
+
+        Err(VarIntDecodeError::Overflow.into())
+    }
+
+    fn read_message(&mut self) -> Result<Option<T>, ProtoError> {
+        let Some(size) = self.read_size()? else {
+            return Ok(None);
+        };
+        self.options.check_message_size(size)?;
+
+        self.buffer.resize(size, 0);
+        self.reader
+            .read_exact(&mut self.buffer)
+            .map_err(|err| match err.kind() {
+                std::io::ErrorKind::UnexpectedEof => ProtoError::UnexpectedEof,
+                _ => err.into(),
+            })?;
+
+        T::decode_message(&mut Decoder::with_options(&self.buffer, self.options)?).map(Some)
+    }
+}
+
+impl<R: Read, T: Message> Iterator for DelimitedReader<R, T> {
+    type Item = Result<T, ProtoError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_message().transpose()
+    }
+}
+
+/// [tokio_util::codec] framing length delimited messages
+#[cfg(feature = "tokio-util")]
+pub struct DelimitedCodec<T> {
+    options: DecodeOptions,
+    _marker: PhantomData<fn() -> T>,
+}
+
+#[cfg(feature = "tokio-util")]
+impl<T> Default for DelimitedCodec<T> {
+    fn default() -> Self {
+        Self::with_options(DecodeOptions::new())
+    }
+}
+
+#[cfg(feature = "tokio-util")]
+impl<T> DelimitedCodec<T> {
+    /// a codec enforcing the given limits, frames exceeding the maximum message size are rejected
+    /// without buffering them
+    pub fn with_options(options: DecodeOptions) -> Self {
+        Self {
+            options,
+            _marker: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "tokio-util")]
+impl<T: Message> tokio_util::codec::Decoder for DelimitedCodec<T> {
+    type Item = T;
+    type Error = ProtoError;
+
+    fn decode(&mut self, src: &mut bytes::BytesMut) -> Result<Option<T>, ProtoError> {
+        use bytes::Buf;
+
+        // the size prefix may be incomplete as well
+        let mut size = 0u64;
+        let mut prefix = None;
+        for (index, byte) in src.iter().take(MAX_VARINT_SIZE).enumerate() {
+            size |= u64::from(byte & 0x7f) << (7 * index);
+            if byte & 0x80 == 0 {
+                prefix = Some(index + 1);
+                break;
+            }
+        }
+        let Some(prefix) = prefix else {
+            return if src.len() >= MAX_VARINT_SIZE {
+                Err(VarIntDecodeError::Overflow.into())
+            } else {
+                Ok(None)
+            };
+        };
+        let size = usize::try_from(size).map_err(|_| VarIntDecodeError::Overflow)?;
+        self.options.check_message_size(size)?;
+
+        if src.len() < prefix + size {
+            src.reserve(prefix + size - src.len());
+            return Ok(None);
+        }
+
+        src.advance(prefix);
+        let frame = src.split_to(size).freeze();
+        T::decode_message(&mut crate::decoder::BytesDecoder::with_options(
+            frame,
+            self.options,
+        )?)
+        .map(Some)
+    }
+}
+
+#[cfg(feature = "tokio-util")]
+impl<T: Message> tokio_util::codec::Encoder<T> for DelimitedCodec<T> {
+    type Error = ProtoError;
+
+    fn encode(&mut self, item: T, dst: &mut bytes::BytesMut) -> Result<(), ProtoError> {
+        let start = dst.len();
+        dst.resize(start + item.length_delimited_size_hint(), 0);
+
+        let mut encoder = crate::encoder::Encoder::new(&mut dst[start..]);
+        item.encode_length_delimited(&mut encoder);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        Decode, Encode, Message, Scalar,
+        decoder::{DecodeOptions, Decoder},
+        encoder::Encoder,
+        error::ProtoError,
+        scalars::ProtoString,
+    };
+
+    use super::DelimitedReader;
+
+    #[derive(Debug, PartialEq)]
+    struct Note(String);
+
+    impl Message for Note {
+        fn encode_message(&self, encoder: &mut impl Encode) {
+            Scalar::<ProtoString>::encode_field(&self.0, 1, encoder);
+        }
+
+        fn decode_message(decoder: &mut impl Decode) -> Result<Self, ProtoError> {
+            decoder.decode_tag()?;
+            Ok(Self(Scalar::<ProtoString>::decode(decoder)?))
+        }
+    }
+
+    fn stream(notes: &[Note]) -> Vec<u8> {
+        let size = notes.iter().map(Message::length_delimited_size_hint).sum();
+        let mut buffer = vec![0u8; size];
+        let mut encoder = Encoder::new(&mut buffer);
+        for note in notes {
+            note.encode_length_delimited(&mut encoder);
+        }
+        buffer
+    }
+
+    #[test]
+    fn length_delimited() {
+        let buffer = stream(&[Note("gin".into()), Note("tonic".into())]);
+        assert_eq!(buffer, b"\x05\x0a\x03gin\x07\x0a\x05tonic");
+
+        let mut decoder = Decoder::new(&buffer);
+        assert_eq!(
+            Note::decode_length_delimited(&mut decoder).unwrap(),
+            Note("gin".into())
+        );
+        assert_eq!(
+            Note::decode_length_delimited(&mut decoder).unwrap(),
+            Note("tonic".into())
+        );
+        assert!(decoder.eof());
+    }
+
+    #[test]
+    fn reader() {
+        let buffer = stream(&[Note("gin".into()), Note("tonic".into())]);
+
+        let notes = DelimitedReader::<_, Note>::new(buffer.as_slice())
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(notes, vec![Note("gin".into()), Note("tonic".into())]);
+
+        let mut truncated = DelimitedReader::<_, Note>::new(&buffer[..buffer.len() - 1]);
+        assert!(truncated.next().unwrap().is_ok());
+        assert!(matches!(
+            truncated.next().unwrap(),
+            Err(ProtoError::UnexpectedEof)
+        ));
+    }
+
+    #[test]
+    fn limits() {
+        let buffer = stream(&[Note("gin".into()), Note("tonic".into())]);
+        let options = DecodeOptions::new().max_message_size(5);
+
+        let mut reader = DelimitedReader::<_, Note>::with_options(buffer.as_slice(), options);
+        assert_eq!(reader.next().unwrap().unwrap(), Note("gin".into()));
+        assert!(matches!(
+            reader.next().unwrap(),
+            Err(ProtoError::MessageTooLarge(5))
+        ));
+
+        let options = DecodeOptions::new().max_length(3);
+        let notes = DelimitedReader::<_, Note>::with_options(buffer.as_slice(), options)
+            .collect::<Vec<_>>();
+        assert!(matches!(notes[1], Err(ProtoError::LengthTooLarge(3))));
+    }
+
+    #[cfg(feature = "tokio-util")]
+    #[test]
+    fn codec() {
+        use tokio_util::codec::{Decoder, Encoder};
+
+        let mut codec = super::DelimitedCodec::<Note>::default();
+        let mut buffer = bytes::BytesMut::new();
+        codec.encode(Note("gin".into()), &mut buffer).unwrap();
+        codec.encode(Note("tonic".into()), &mut buffer).unwrap();
+
+        // partial frames wait for more data
+        let mut partial = buffer.split_to(3);
+        assert_eq!(codec.decode(&mut partial).unwrap(), None);
+        partial.unsplit(buffer);
+
+        assert_eq!(
+            codec.decode(&mut partial).unwrap(),
+            Some(Note("gin".into()))
+        );
+        assert_eq!(
+            codec.decode(&mut partial).unwrap(),
+            Some(Note("tonic".into()))
+        );
+        assert_eq!(codec.decode(&mut partial).unwrap(), None);
+    }
+}
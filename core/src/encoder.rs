@@ -3,6 +3,7 @@ use crate::Encode;
 pub struct Encoder<'buf> {
     buffer: &'buf mut [u8],
     position: usize,
+    deterministic: bool,
 }
 
 impl<'buf> Encoder<'buf> {
@@ -11,6 +12,17 @@ impl<'buf> Encoder<'buf> {
         Self {
             buffer,
             position: 0,
+            deterministic: false,
+        }
+    }
+
+    /// encoder producing identical bytes for identical messages, see [Encode::deterministic]
+    #[inline]
+    pub fn new_deterministic(buffer: &'buf mut [u8]) -> Self {
+        Self {
+            buffer,
+            position: 0,
+            deterministic: true,
         }
     }
 
@@ -31,6 +43,11 @@ impl<'buf> Encoder<'buf> {
 }
 
 impl<'buf> Encode for Encoder<'buf> {
+    #[inline]
+    fn deterministic(&self) -> bool {
+        self.deterministic
+    }
+
     #[inline]
     fn encode_sint32(&mut self, n: i32) {
         let (data, size) = varint_simd::encode_zigzag(n);
@@ -105,8 +122,9 @@ impl<'buf> Encode for Encoder<'buf> {
     }
 }
 
+/// encoder counting the bytes that would be written, e.g. to size a buffer or a length prefix
 #[derive(Debug, Default)]
-pub(crate) struct SizeHint {
+pub struct SizeHint {
     size: usize,
 }
 
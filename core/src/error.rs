@@ -10,12 +10,66 @@ pub enum ProtoError {
     Utf8(#[from] Utf8Error),
     #[error(transparent)]
     ArrayFromSlice(#[from] TryFromSliceError),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
     #[error("Field number {0} is missing")]
     MissingField(u32),
     #[error("No OneOf variants found, expected one of: {0:?}")]
     MissingOneOf(&'static [u32]),
     #[error("{0} is not a known enum variant")]
     UnknownEnumVariant(i32),
+    #[error("{0} is not a known enum value name")]
+    UnknownEnumName(String),
+    #[error("{0} is not a known wire type")]
+    UnknownWireType(u8),
+    #[error("Unexpected end of buffer")]
+    UnexpectedEof,
+    #[error("Unexpected end of group for field number {0}")]
+    UnexpectedEndGroup(u32),
+    #[error("Message size exceeds the limit of {0} bytes")]
+    MessageTooLarge(usize),
+    #[error("Length exceeds the limit of {0} bytes")]
+    LengthTooLarge(usize),
+    #[error("Repeated field exceeds the limit of {0} elements")]
+    TooManyElements(usize),
+    #[error("Nesting of messages and groups exceeds the limit of {0} levels")]
+    RecursionLimitExceeded(usize),
+    /// error while decoding a field, `path` starts at the outermost message, e.g.
+    /// `EchoResponse.echo.ip`
+    #[error("{path}: {source}")]
+    Field {
+        path: String,
+        #[source]
+        source: Box<ProtoError>,
+    },
     #[error("{0}")]
     Custom(String),
 }
+
+impl ProtoError {
+    /// add the field of a message to the path of the error
+    pub fn in_field(self, message: &str, field: &str) -> Self {
+        match self {
+            ProtoError::Field { path, source } => {
+                // the path of a nested error starts with the type name of the nested message
+                let rest = path.split_once('.').map_or(path.as_str(), |(_, rest)| rest);
+                ProtoError::Field {
+                    path: format!("{message}.{field}.{rest}"),
+                    source,
+                }
+            }
+            err => ProtoError::Field {
+                path: format!("{message}.{field}"),
+                source: Box::new(err),
+            },
+        }
+    }
+
+    /// path of the field that failed to decode
+    pub fn path(&self) -> Option<&str> {
+        match self {
+            ProtoError::Field { path, .. } => Some(path),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,342 @@
+//! storage for proto2 extensions and other fields not known to a message
+
+use std::{collections::BTreeMap, marker::PhantomData};
+
+use crate::{
+    Decode, Encode, Scalar, Tag,
+    decoder::Decoder,
+    encoder::{Encoder, SizeHint},
+    error::ProtoError,
+    wire_types::{
+        WIRE_TYPE_END_GROUP, WIRE_TYPE_I32, WIRE_TYPE_I64, WIRE_TYPE_LENGTH_ENCODED,
+        WIRE_TYPE_START_GROUP, WIRE_TYPE_VARINT,
+    },
+};
+
+/// a field value kept in its wire representation
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RawField {
+    Varint(u64),
+    I64(u64),
+    LengthEncoded(Vec<u8>),
+    I32(u32),
+    /// fields of a group, the end group tag is written by [ExtensionSet::encode]
+    Group(ExtensionSet),
+}
+
+impl RawField {
+    pub fn wire_type(&self) -> u8 {
+        match self {
+            RawField::Varint(_) => WIRE_TYPE_VARINT,
+            RawField::I64(_) => WIRE_TYPE_I64,
+            RawField::LengthEncoded(_) => WIRE_TYPE_LENGTH_ENCODED,
+            RawField::I32(_) => WIRE_TYPE_I32,
+            RawField::Group(_) => WIRE_TYPE_START_GROUP,
+        }
+    }
+
+    /// decode the value following the tag
+    pub fn decode(tag: Tag, decoder: &mut impl Decode) -> Result<Self, ProtoError> {
+        match tag.wire_type() {
+            WIRE_TYPE_VARINT => Ok(RawField::Varint(decoder.decode_uint64()?)),
+            WIRE_TYPE_I64 => Ok(RawField::I64(decoder.decode_fixed64()?)),
+            WIRE_TYPE_LENGTH_ENCODED => Ok(RawField::LengthEncoded(decoder.decode_bytes()?)),
+            WIRE_TYPE_I32 => Ok(RawField::I32(decoder.decode_fixed32()?)),
+            WIRE_TYPE_START_GROUP => decoder.nested(|decoder| {
+                let mut group = ExtensionSet::default();
+                loop {
+                    if decoder.eof() {
+                        return Err(ProtoError::UnexpectedEof);
+                    }
+                    let inner = decoder.decode_tag()?;
+                    if inner.wire_type() == WIRE_TYPE_END_GROUP
+                        && inner.field_number() == tag.field_number()
+                    {
+                        return Ok(RawField::Group(group));
+                    }
+                    group.decode_field(inner, decoder)?;
+                }
+            }),
+            WIRE_TYPE_END_GROUP => Err(ProtoError::UnexpectedEndGroup(tag.field_number())),
+            wire_type => Err(ProtoError::UnknownWireType(wire_type)),
+        }
+    }
+
+    /// encode the value without its tag, groups are encoded without their end group tag
+    pub fn encode(&self, encoder: &mut impl Encode) {
+        match self {
+            RawField::Varint(v) => encoder.encode_uint64(*v),
+            RawField::I64(v) => encoder.encode_fixed64(*v),
+            RawField::LengthEncoded(v) => encoder.encode_bytes(v),
+            RawField::I32(v) => encoder.encode_fixed32(*v),
+            RawField::Group(group) => group.encode(encoder),
+        }
+    }
+
+    fn from_scalar<P, T: Scalar<P>>(value: &T) -> Result<Self, ProtoError> {
+        let mut buffer = vec![0u8; value.size_hint()];
+        let mut encoder = Encoder::new(&mut buffer);
+        value.encode(&mut encoder);
+
+        RawField::decode(Tag::from_parts(0, T::WIRE_TYPE), &mut Decoder::new(&buffer))
+    }
+
+    fn to_scalar<P, T: Scalar<P>>(&self) -> Result<T, ProtoError> {
+        if let RawField::Group(_) = self {
+            return Err(ProtoError::Custom(String::from(
+                "group extensions can't be read as scalar",
+            )));
+        }
+
+        let mut hint = SizeHint::default();
+        self.encode(&mut hint);
+        let mut buffer = vec![0u8; hint.size()];
+        let mut encoder = Encoder::new(&mut buffer);
+        self.encode(&mut encoder);
+
+        T::decode(&mut Decoder::new(&buffer))
+    }
+}
+
+/// typed descriptor of an extension field, generated for `extend` declarations
+pub struct Extension<ProtobufType, RustType> {
+    number: u32,
+    _marker: PhantomData<fn() -> (ProtobufType, RustType)>,
+}
+
+impl<ProtobufType, RustType> Extension<ProtobufType, RustType> {
+    pub const fn new(number: u32) -> Self {
+        Self {
+            number,
+            _marker: PhantomData,
+        }
+    }
+
+    pub const fn number(&self) -> u32 {
+        self.number
+    }
+}
+
+/// extension fields of a message, fields are kept in their wire representation and converted
+/// when accessed through an [Extension]. fields unknown to the message are kept as well and
+/// written back when the message is encoded
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ExtensionSet {
+    fields: BTreeMap<u32, Vec<RawField>>,
+}
+
+impl ExtensionSet {
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    pub fn has<P, T>(&self, extension: &Extension<P, T>) -> bool {
+        self.fields.contains_key(&extension.number)
+    }
+
+    /// the value of a singular extension, the last occurrence wins
+    pub fn get<P, T: Scalar<P>>(
+        &self,
+        extension: &Extension<P, T>,
+    ) -> Result<Option<T>, ProtoError> {
+        self.fields
+            .get(&extension.number)
+            .and_then(|values| values.last())
+            .map(RawField::to_scalar)
+            .transpose()
+    }
+
+    /// all values of a repeated extension
+    pub fn get_repeated<P, T: Scalar<P>>(
+        &self,
+        extension: &Extension<P, T>,
+    ) -> Result<Vec<T>, ProtoError> {
+        self.fields
+            .get(&extension.number)
+            .map(|values| values.iter().map(RawField::to_scalar).collect())
+            .unwrap_or_else(|| Ok(Vec::new()))
+    }
+
+    /// replace the value of an extension
+    pub fn set<P, T: Scalar<P>>(
+        &mut self,
+        extension: &Extension<P, T>,
+        value: &T,
+    ) -> Result<(), ProtoError> {
+        let raw = RawField::from_scalar(value)?;
+        self.fields.insert(extension.number, vec![raw]);
+        Ok(())
+    }
+
+    /// append a value to a repeated extension
+    pub fn push<P, T: Scalar<P>>(
+        &mut self,
+        extension: &Extension<P, T>,
+        value: &T,
+    ) -> Result<(), ProtoError> {
+        let raw = RawField::from_scalar(value)?;
+        self.fields.entry(extension.number).or_default().push(raw);
+        Ok(())
+    }
+
+    pub fn clear<P, T>(&mut self, extension: &Extension<P, T>) {
+        self.fields.remove(&extension.number);
+    }
+
+    /// keep a field the message does not know about
+    pub fn decode_field(&mut self, tag: Tag, decoder: &mut impl Decode) -> Result<(), ProtoError> {
+        let raw = RawField::decode(tag, decoder)?;
+        self.fields.entry(tag.field_number()).or_default().push(raw);
+        Ok(())
+    }
+
+    pub fn encode(&self, encoder: &mut impl Encode) {
+        for (number, values) in &self.fields {
+            for value in values {
+                encoder.encode_tag(Tag::from_parts(*number, value.wire_type()));
+                value.encode(encoder);
+                if let RawField::Group(_) = value {
+                    encoder.encode_tag(Tag::from_parts(*number, WIRE_TYPE_END_GROUP));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        Message,
+        decoder::Decoder,
+        scalars::{Int32, ProtoString, SInt64},
+    };
+
+    use super::*;
+
+    const NAME: Extension<ProtoString, String> = Extension::new(100);
+    const OFFSET: Extension<SInt64, i64> = Extension::new(101);
+    const IDS: Extension<Int32, i32> = Extension::new(102);
+
+    #[derive(Debug, Default, PartialEq)]
+    struct Extendable {
+        extensions: ExtensionSet,
+    }
+
+    impl Message for Extendable {
+        fn encode_message(&self, encoder: &mut impl Encode) {
+            self.extensions.encode(encoder);
+        }
+
+        fn decode_message(decoder: &mut impl Decode) -> Result<Self, ProtoError> {
+            let mut extensions = ExtensionSet::default();
+            while !decoder.eof() {
+                let tag = decoder.decode_tag()?;
+                extensions.decode_field(tag, decoder)?;
+            }
+            Ok(Self { extensions })
+        }
+    }
+
+    #[test]
+    fn accessors() {
+        let mut set = ExtensionSet::default();
+        assert!(set.is_empty());
+        assert_eq!(set.get(&NAME).unwrap(), None);
+
+        set.set(&NAME, &String::from("gin")).unwrap();
+        set.set(&OFFSET, &-3).unwrap();
+        set.push(&IDS, &1).unwrap();
+        set.push(&IDS, &2).unwrap();
+
+        assert!(set.has(&NAME));
+        assert_eq!(set.get(&NAME).unwrap(), Some(String::from("gin")));
+        assert_eq!(set.get(&OFFSET).unwrap(), Some(-3));
+        assert_eq!(set.get_repeated(&IDS).unwrap(), vec![1, 2]);
+
+        set.clear(&NAME);
+        assert!(!set.has(&NAME));
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut message = Extendable::default();
+        message
+            .extensions
+            .set(&NAME, &String::from("tonic"))
+            .unwrap();
+        message.extensions.set(&OFFSET, &-150).unwrap();
+
+        let mut buffer = vec![0u8; message.message_size_hint()];
+        let mut encoder = Encoder::new(&mut buffer);
+        message.encode_message(&mut encoder);
+
+        assert_eq!(buffer, b"\xa2\x06\x05tonic\xa8\x06\xab\x02");
+
+        let mut decoder = Decoder::new(&buffer);
+        let read = Extendable::decode_message(&mut decoder).unwrap();
+        assert_eq!(read, message);
+        assert_eq!(read.extensions.get(&OFFSET).unwrap(), Some(-150));
+    }
+
+    #[test]
+    fn unknown_wire_type() {
+        let mut set = ExtensionSet::default();
+        let mut decoder = Decoder::new(b"\x00");
+
+        let err = set
+            .decode_field(Tag::from_parts(1, 7), &mut decoder)
+            .unwrap_err();
+        assert!(matches!(err, ProtoError::UnknownWireType(7)));
+    }
+
+    #[test]
+    fn groups() {
+        // group 3 { 1: 150, group 2 { 1: "a" } }, 4: 1
+        let buffer = b"\x1b\x08\x96\x01\x13\x0a\x01a\x14\x1c\x20\x01";
+
+        let mut decoder = Decoder::new(buffer);
+        let read = Extendable::decode_message(&mut decoder).unwrap();
+
+        let mut out = vec![0u8; read.message_size_hint()];
+        let mut encoder = Encoder::new(&mut out);
+        read.encode_message(&mut encoder);
+        assert_eq!(&out, buffer);
+
+        // skipping consumes the whole group including the end tag
+        let mut decoder = Decoder::new(buffer);
+        let tag = decoder.decode_tag().unwrap();
+        decoder.skip_field(tag).unwrap();
+        assert_eq!(
+            decoder.decode_tag().unwrap(),
+            Tag::from_parts(4, WIRE_TYPE_VARINT)
+        );
+    }
+
+    #[test]
+    fn unterminated_group() {
+        let mut decoder = Decoder::new(b"\x08\x01");
+        let err = decoder
+            .skip_field(Tag::from_parts(3, WIRE_TYPE_START_GROUP))
+            .unwrap_err();
+        assert!(matches!(err, ProtoError::UnexpectedEof));
+
+        let mut decoder = Decoder::new(b"\x24");
+        let err = decoder
+            .skip_field(Tag::from_parts(3, WIRE_TYPE_START_GROUP))
+            .unwrap_err();
+        assert!(matches!(err, ProtoError::UnexpectedEndGroup(4)));
+    }
+
+    #[test]
+    fn nested_groups() {
+        // unknown groups nested deep enough to overflow the stack without a limit
+        let mut buffer = vec![0x0b; 200_000];
+        buffer.extend(vec![0x0c; 200_000]);
+
+        let mut set = ExtensionSet::default();
+        let mut decoder = Decoder::new(&buffer);
+        let tag = decoder.decode_tag().unwrap();
+        let err = set.decode_field(tag, &mut decoder).unwrap_err();
+        assert!(matches!(err, ProtoError::RecursionLimitExceeded(100)));
+    }
+}
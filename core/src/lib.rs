@@ -1,6 +1,8 @@
 pub mod decoder;
+pub mod delimited;
 pub mod encoder;
 mod error;
+mod extension;
 mod map;
 mod message;
 mod repeated;
@@ -8,10 +10,20 @@ pub mod scalars;
 mod tag;
 mod traits;
 pub mod types;
+mod view;
 mod wire_types;
 
+#[cfg(feature = "bytes")]
+pub use bytes;
 pub use error::ProtoError;
+pub use extension::{Extension, ExtensionSet, RawField};
 pub use fxhash;
+#[cfg(feature = "indexmap")]
+pub use indexmap;
 pub use tag::Tag;
 pub use traits::{Decode, Encode, Map, Message, PackableMarker, Packed, Scalar, Unpacked};
-pub use wire_types::{WIRE_TYPE_I32, WIRE_TYPE_I64, WIRE_TYPE_LENGTH_ENCODED, WIRE_TYPE_VARINT};
+pub use view::{RawFields, RawMessageView};
+pub use wire_types::{
+    WIRE_TYPE_END_GROUP, WIRE_TYPE_I32, WIRE_TYPE_I64, WIRE_TYPE_LENGTH_ENCODED,
+    WIRE_TYPE_START_GROUP, WIRE_TYPE_VARINT,
+};
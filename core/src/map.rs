@@ -1,9 +1,12 @@
-use std::hash::Hash;
-
-use fxhash::FxHashMap;
+use std::{
+    collections::{BTreeMap, HashMap},
+    hash::{BuildHasher, Hash},
+};
 
 use crate::{
-    Decode, Encode, Map, Scalar, Tag, encoder::SizeHint, error::ProtoError,
+    Decode, Encode, Map, Scalar, Tag,
+    encoder::{Encoder, SizeHint},
+    error::ProtoError,
     wire_types::WIRE_TYPE_LENGTH_ENCODED,
 };
 
@@ -38,47 +41,316 @@ impl<'p, RustKey, RustValue> KeyValuePairView<'p, RustKey, RustValue> {
     }
 }
 
-impl<RustKey, ProtobufKey, RustValue, ProtobufValue> Map<ProtobufKey, ProtobufValue>
-    for FxHashMap<RustKey, RustValue>
+/// encodes every entry in iteration order
+#[inline]
+fn encode_entries<'p, RustKey, ProtobufKey, RustValue, ProtobufValue>(
+    entries: impl Iterator<Item = (&'p RustKey, &'p RustValue)>,
+    field_number: u32,
+    encoder: &mut impl Encode,
+) where
+    RustKey: Scalar<ProtobufKey> + 'p,
+    RustValue: Scalar<ProtobufValue> + 'p,
+{
+    for (key, value) in entries {
+        encode_entry(key, value, field_number, encoder);
+    }
+}
+
+/// encodes the entries sorted by their encoded key if the encoder is deterministic, which
+/// doesn't need the key to implement `Ord`
+#[inline]
+fn encode_unordered_entries<'p, RustKey, ProtobufKey, RustValue, ProtobufValue>(
+    entries: impl Iterator<Item = (&'p RustKey, &'p RustValue)>,
+    field_number: u32,
+    encoder: &mut impl Encode,
+) where
+    RustKey: Scalar<ProtobufKey> + 'p,
+    RustValue: Scalar<ProtobufValue> + 'p,
+{
+    if encoder.deterministic() {
+        let mut entries = entries
+            .map(|entry| (encode_key(entry.0), entry))
+            .collect::<Vec<_>>();
+        entries.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+        encode_entries(
+            entries.into_iter().map(|(_, entry)| entry),
+            field_number,
+            encoder,
+        );
+    } else {
+        encode_entries(entries, field_number, encoder);
+    }
+}
+
+/// the wire bytes of a key, equal keys have equal bytes
+fn encode_key<RustKey, ProtobufKey>(key: &RustKey) -> Vec<u8>
+where
+    RustKey: Scalar<ProtobufKey>,
+{
+    let mut buffer = vec![0u8; key.size_hint()];
+    key.encode(&mut Encoder::new(&mut buffer));
+    buffer
+}
+
+/// encodes an entry as length encoded key value pair message
+#[inline]
+fn encode_entry<RustKey, ProtobufKey, RustValue, ProtobufValue>(
+    key: &RustKey,
+    value: &RustValue,
+    field_number: u32,
+    encoder: &mut impl Encode,
+) where
+    RustKey: Scalar<ProtobufKey>,
+    RustValue: Scalar<ProtobufValue>,
+{
+    encoder.encode_tag(Tag::from_parts(field_number, WIRE_TYPE_LENGTH_ENCODED));
+
+    let pair = KeyValuePairView { key, value };
+
+    encoder.encode_uint64(pair.size_hint() as u64);
+    pair.encode(encoder);
+}
+
+/// decodes a single length encoded key value pair message
+#[inline]
+#[allow(clippy::cast_possible_truncation)]
+fn decode_entry<RustKey, ProtobufKey, RustValue, ProtobufValue>(
+    decoder: &mut impl Decode,
+) -> Result<(RustKey, RustValue), ProtoError>
+where
+    RustKey: Scalar<ProtobufKey>,
+    RustValue: Scalar<ProtobufValue>,
+{
+    let size = decoder.decode_uint64()? as usize;
+    let mut entry = decoder.sub_decoder(size)?;
+
+    let tag1 = entry.decode_tag()?;
+    if tag1.field_number() == 1 {
+        let key = <RustKey as Scalar<ProtobufKey>>::decode(&mut entry)?;
+        entry.decode_tag()?;
+        let value = <RustValue as Scalar<ProtobufValue>>::decode(&mut entry)?;
+        Ok((key, value))
+    } else {
+        let value = <RustValue as Scalar<ProtobufValue>>::decode(&mut entry)?;
+        entry.decode_tag()?;
+        let key = <RustKey as Scalar<ProtobufKey>>::decode(&mut entry)?;
+        Ok((key, value))
+    }
+}
+
+// covers `FxHashMap` as well as the std hasher and any other `BuildHasher`
+impl<RustKey, ProtobufKey, RustValue, ProtobufValue, S> Map<ProtobufKey, ProtobufValue>
+    for HashMap<RustKey, RustValue, S>
 where
     RustKey: Scalar<ProtobufKey> + Hash + Eq,
     RustValue: Scalar<ProtobufValue>,
+    S: BuildHasher,
 {
     #[inline]
     fn encode(&self, field_number: u32, encoder: &mut impl Encode) {
-        for (key, value) in self.iter() {
-            encoder.encode_tag(Tag::from_parts(field_number, WIRE_TYPE_LENGTH_ENCODED));
+        encode_unordered_entries(self.iter(), field_number, encoder);
+    }
 
-            let pair = KeyValuePairView { key, value };
+    #[inline]
+    fn decode(decoder: &mut impl Decode, map: &mut Self) -> Result<(), ProtoError>
+    where
+        Self: Sized,
+    {
+        decoder.options().check_repeated(map.len())?;
+        let (key, value) = decode_entry(decoder)?;
+        map.insert(key, value);
+        Ok(())
+    }
+}
 
-            encoder.encode_uint64(pair.size_hint() as u64);
-            pair.encode(encoder);
-        }
+impl<RustKey, ProtobufKey, RustValue, ProtobufValue> Map<ProtobufKey, ProtobufValue>
+    for BTreeMap<RustKey, RustValue>
+where
+    RustKey: Scalar<ProtobufKey> + Ord,
+    RustValue: Scalar<ProtobufValue>,
+{
+    #[inline]
+    fn encode(&self, field_number: u32, encoder: &mut impl Encode) {
+        encode_entries(self.iter(), field_number, encoder);
     }
 
     #[inline]
-    #[allow(clippy::cast_possible_truncation)]
-    fn decode<'buf>(decoder: &mut impl Decode, map: &mut Self) -> Result<(), ProtoError>
+    fn decode(decoder: &mut impl Decode, map: &mut Self) -> Result<(), ProtoError>
     where
         Self: Sized,
     {
-        let size = decoder.decode_uint64()? as usize;
-        let mut entry = decoder.sub_decoder(size);
-
-        let tag1 = entry.decode_tag()?;
-        let (key, value) = if tag1.field_number() == 1 {
-            let key = <RustKey as Scalar<ProtobufKey>>::decode(&mut entry)?;
-            entry.decode_tag()?;
-            let value = <RustValue as Scalar<ProtobufValue>>::decode(&mut entry)?;
-            (key, value)
-        } else {
-            let value = <RustValue as Scalar<ProtobufValue>>::decode(&mut entry)?;
-            entry.decode_tag()?;
-            let key = <RustKey as Scalar<ProtobufKey>>::decode(&mut entry)?;
-            (key, value)
-        };
+        decoder.options().check_repeated(map.len())?;
+        let (key, value) = decode_entry(decoder)?;
+        map.insert(key, value);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "indexmap")]
+impl<RustKey, ProtobufKey, RustValue, ProtobufValue, S> Map<ProtobufKey, ProtobufValue>
+    for indexmap::IndexMap<RustKey, RustValue, S>
+where
+    RustKey: Scalar<ProtobufKey> + Hash + Eq,
+    RustValue: Scalar<ProtobufValue>,
+    S: BuildHasher,
+{
+    #[inline]
+    fn encode(&self, field_number: u32, encoder: &mut impl Encode) {
+        encode_unordered_entries(self.iter(), field_number, encoder);
+    }
 
+    #[inline]
+    fn decode(decoder: &mut impl Decode, map: &mut Self) -> Result<(), ProtoError>
+    where
+        Self: Sized,
+    {
+        decoder.options().check_repeated(map.len())?;
+        let (key, value) = decode_entry(decoder)?;
         map.insert(key, value);
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::collections::{BTreeMap, HashMap};
+
+    use crate::{Decode, Encode, Map, Scalar, decoder::Decoder, encoder::Encoder, scalars::*};
+
+    #[test]
+    fn btree_map() {
+        let mut map = BTreeMap::new();
+        map.insert(2u32, String::from("two"));
+        map.insert(1u32, String::from("one"));
+
+        let size_hint = Map::<UInt32, ProtoString>::size_hint(&map, 1);
+        let mut buffer = vec![0u8; size_hint];
+        let mut encoder = Encoder::new(&mut buffer);
+        Map::<UInt32, ProtoString>::encode(&map, 1, &mut encoder);
+
+        assert_eq!(size_hint, buffer.len());
+        assert_eq!(
+            &buffer,
+            b"\x0a\x07\x08\x01\x12\x03one\x0a\x07\x08\x02\x12\x03two"
+        );
+
+        let mut decoder = Decoder::new(&buffer);
+        let mut deserialized = BTreeMap::new();
+        while !decoder.eof() {
+            decoder.decode_tag().unwrap();
+            Map::<UInt32, ProtoString>::decode(&mut decoder, &mut deserialized).unwrap();
+        }
+
+        assert_eq!(map, deserialized);
+    }
+
+    #[test]
+    fn std_hash_map() {
+        let mut map = HashMap::new();
+        map.insert(String::from("one"), 1u32);
+        map.insert(String::from("two"), 2u32);
+
+        let size_hint = Map::<ProtoString, UInt32>::size_hint(&map, 1);
+        let mut buffer = vec![0u8; size_hint];
+        let mut encoder = Encoder::new(&mut buffer);
+        Map::<ProtoString, UInt32>::encode(&map, 1, &mut encoder);
+
+        assert_eq!(size_hint, buffer.len());
+
+        let mut decoder = Decoder::new(&buffer);
+        let mut deserialized = HashMap::new();
+        while !decoder.eof() {
+            decoder.decode_tag().unwrap();
+            Map::<ProtoString, UInt32>::decode(&mut decoder, &mut deserialized).unwrap();
+        }
+
+        assert_eq!(map, deserialized);
+    }
+
+    #[test]
+    fn deterministic_hash_map() {
+        let encode = |map: &HashMap<String, u32>| {
+            let size_hint = Map::<ProtoString, UInt32>::size_hint(map, 1);
+            let mut buffer = vec![0u8; size_hint];
+            let mut encoder = Encoder::new_deterministic(&mut buffer);
+            Map::<ProtoString, UInt32>::encode(map, 1, &mut encoder);
+            buffer
+        };
+
+        // every map uses its own random hasher state, so iteration orders differ between them
+        let expected = encode(&(0..64).map(|n| (n.to_string(), n)).collect());
+        for _ in 0..16 {
+            let map = (0..64).rev().map(|n| (n.to_string(), n)).collect();
+            assert_eq!(expected, encode(&map));
+        }
+
+        // sorted by the encoded key, the length prefix comes first
+        let mut decoder = Decoder::new(&expected);
+        let mut keys = vec![];
+        while !decoder.eof() {
+            decoder.decode_tag().unwrap();
+            let mut entry = HashMap::<String, u32>::new();
+            Map::<ProtoString, UInt32>::decode(&mut decoder, &mut entry).unwrap();
+            keys.extend(entry.into_keys());
+        }
+        assert!(keys.is_sorted_by_key(|key| (key.len(), key.clone())));
+    }
+
+    #[test]
+    fn unordered_key() {
+        // hash map keys only need `Hash + Eq`, also for deterministic encoding
+        #[derive(Debug, Hash, PartialEq, Eq)]
+        struct Key(u32);
+
+        impl Scalar<UInt32> for Key {
+            const WIRE_TYPE: u8 = crate::WIRE_TYPE_VARINT;
+
+            fn encode(&self, encoder: &mut impl Encode) {
+                encoder.encode_uint32(self.0);
+            }
+
+            fn decode(decoder: &mut impl Decode) -> Result<Self, crate::ProtoError> {
+                decoder.decode_uint32().map(Key)
+            }
+        }
+
+        let map = HashMap::from([(Key(2), 20u32), (Key(1), 10u32)]);
+        let size_hint = Map::<UInt32, UInt32>::size_hint(&map, 1);
+        let mut buffer = vec![0u8; size_hint];
+        let mut encoder = Encoder::new_deterministic(&mut buffer);
+        Map::<UInt32, UInt32>::encode(&map, 1, &mut encoder);
+
+        assert_eq!(&buffer, b"\x0a\x04\x08\x01\x10\x0a\x0a\x04\x08\x02\x10\x14");
+    }
+
+    #[cfg(feature = "indexmap")]
+    #[test]
+    fn index_map() {
+        let mut map = indexmap::IndexMap::new();
+        map.insert(String::from("b"), 2u32);
+        map.insert(String::from("a"), 1u32);
+
+        let size_hint = Map::<ProtoString, UInt32>::size_hint(&map, 1);
+        let mut buffer = vec![0u8; size_hint];
+        let mut encoder = Encoder::new(&mut buffer);
+        Map::<ProtoString, UInt32>::encode(&map, 1, &mut encoder);
+
+        // insertion order is kept on the wire
+        assert_eq!(
+            &buffer,
+            b"\x0a\x05\x0a\x01b\x10\x02\x0a\x05\x0a\x01a\x10\x01"
+        );
+
+        let mut decoder = Decoder::new(&buffer);
+        let mut deserialized = indexmap::IndexMap::new();
+        while !decoder.eof() {
+            decoder.decode_tag().unwrap();
+            Map::<ProtoString, UInt32>::decode(&mut decoder, &mut deserialized).unwrap();
+        }
+
+        assert_eq!(
+            map.iter().collect::<Vec<_>>(),
+            deserialized.iter().collect::<Vec<_>>()
+        );
+    }
+}
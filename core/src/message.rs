@@ -38,8 +38,20 @@ where
         Self: Sized,
     {
         let size = <u64 as Scalar<UInt64>>::decode(decoder)? as usize;
+        decoder
+            .sub_decoder(size)?
+            .nested(|decoder| <Self as Message>::decode_message(decoder))
+    }
 
-        <Self as Message>::decode_message(&mut decoder.sub_decoder(size))
+    #[allow(clippy::cast_possible_truncation)]
+    fn merge(&mut self, decoder: &mut impl Decode) -> Result<(), ProtoError>
+    where
+        Self: Sized,
+    {
+        let size = <u64 as Scalar<UInt64>>::decode(decoder)? as usize;
+        decoder
+            .sub_decoder(size)?
+            .nested(|decoder| <Self as Message>::merge_message(self, decoder))
     }
 }
 
@@ -33,8 +33,12 @@ where
         Self: Sized,
     {
         let size = decoder.decode_uint64()? as usize;
+        if decoder.buffer().len() < size {
+            return Err(crate::ProtoError::UnexpectedEof);
+        }
         let end = decoder.position() + size;
         while decoder.position() < end {
+            decoder.options().check_repeated(v.len())?;
             v.push(Scalar::<ProtobufType>::decode(decoder)?);
         }
 
@@ -48,6 +48,9 @@ pub struct Bool;
 
 pub struct ProtoString;
 
+/// `string` decoded without failing on invalid UTF-8, invalid sequences are replaced by `U+FFFD`
+pub struct LossyString;
+
 pub struct Bytes;
 
 #[cfg(test)]
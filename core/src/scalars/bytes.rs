@@ -15,8 +15,25 @@ impl Scalar<super::Bytes> for Vec<u8> {
     }
 }
 
+/// fixed size byte arrays, decoding fails if the length does not match
+impl<const N: usize> Scalar<super::Bytes> for [u8; N] {
+    const WIRE_TYPE: u8 = WIRE_TYPE_LENGTH_ENCODED;
+
+    fn encode(&self, encoder: &mut impl crate::Encode) {
+        encoder.encode_bytes(self);
+    }
+
+    fn decode(decoder: &mut impl crate::Decode) -> Result<Self, crate::error::ProtoError>
+    where
+        Self: Sized,
+    {
+        Ok(decoder.decode_bytes()?.as_slice().try_into()?)
+    }
+}
+
 #[cfg(test)]
 mod test {
+    use crate::{Scalar, decoder::Decoder, scalars::Bytes};
 
     #[test]
     fn encode_decode() {
@@ -48,4 +65,19 @@ mod test {
             );
         }
     }
+
+    #[test]
+    fn encode_decode_array() {
+        crate::scalars::test_scalar_encode_decode::<_, crate::scalars::Bytes>(
+            [1u8, 2, 3, 4],
+            5,
+            b"\x04\x01\x02\x03\x04",
+        );
+    }
+
+    #[test]
+    fn decode_array_length_mismatch() {
+        let mut decoder = Decoder::new(b"\x03\x01\x02\x03");
+        assert!(<[u8; 4] as Scalar<Bytes>>::decode(&mut decoder).is_err());
+    }
 }
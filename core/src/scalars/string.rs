@@ -15,6 +15,21 @@ impl Scalar<super::ProtoString> for String {
     }
 }
 
+impl Scalar<super::LossyString> for String {
+    const WIRE_TYPE: u8 = WIRE_TYPE_LENGTH_ENCODED;
+
+    fn encode(&self, encoder: &mut impl crate::Encode) {
+        encoder.encode_str(self);
+    }
+
+    fn decode(decoder: &mut impl crate::Decode) -> Result<Self, crate::error::ProtoError>
+    where
+        Self: Sized,
+    {
+        decoder.decode_string_lossy()
+    }
+}
+
 #[cfg(test)]
 mod test {
 
@@ -51,4 +66,21 @@ mod test {
             );
         }
     }
+
+    #[test]
+    fn invalid_utf8() {
+        use crate::{Scalar, decoder::Decoder, error::ProtoError, scalars};
+
+        let buffer = b"\x04gin\xff";
+
+        let strict = <String as Scalar<scalars::ProtoString>>::decode(&mut Decoder::new(buffer));
+        assert!(matches!(strict, Err(ProtoError::Utf8(_))));
+
+        let lossy =
+            <String as Scalar<scalars::LossyString>>::decode(&mut Decoder::new(buffer)).unwrap();
+        assert_eq!(lossy, "gin\u{fffd}");
+
+        let raw = <Vec<u8> as Scalar<scalars::Bytes>>::decode(&mut Decoder::new(buffer)).unwrap();
+        assert_eq!(raw, b"gin\xff");
+    }
 }
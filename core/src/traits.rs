@@ -1,4 +1,13 @@
-use crate::{Tag, encoder::SizeHint, error::ProtoError};
+use crate::{
+    Tag,
+    decoder::{DecodeOptions, GroupDecoder},
+    encoder::{Encoder, SizeHint},
+    error::ProtoError,
+    wire_types::{
+        WIRE_TYPE_END_GROUP, WIRE_TYPE_I32, WIRE_TYPE_I64, WIRE_TYPE_LENGTH_ENCODED,
+        WIRE_TYPE_START_GROUP, WIRE_TYPE_VARINT,
+    },
+};
 
 pub trait Scalar<ProtobufType> {
     const WIRE_TYPE: u8;
@@ -14,6 +23,16 @@ pub trait Scalar<ProtobufType> {
     where
         Self: Sized;
 
+    /// decode into an existing value, messages merge while everything else takes the last value
+    #[inline]
+    fn merge(&mut self, decoder: &mut impl Decode) -> Result<(), ProtoError>
+    where
+        Self: Sized,
+    {
+        *self = Self::decode(decoder)?;
+        Ok(())
+    }
+
     /// helper to serialize type with field number
     #[inline]
     fn encode_field(&self, field_number: u32, encoder: &mut impl Encode) {
@@ -67,6 +86,23 @@ pub trait Encode {
     fn encode_string(&mut self, s: String) {
         self.encode_bytes(s.as_bytes())
     }
+
+    /// encode a message as group, delimited by start and end group tags instead of a length
+    fn encode_group(&mut self, field_number: u32, message: &impl Message)
+    where
+        Self: Sized,
+    {
+        self.encode_tag(Tag::from_parts(field_number, WIRE_TYPE_START_GROUP));
+        message.encode_message(self);
+        self.encode_tag(Tag::from_parts(field_number, WIRE_TYPE_END_GROUP));
+    }
+
+    /// when `true` encoding produces identical bytes for identical messages, e.g. entries of hash
+    /// maps are sorted by their encoded key instead of using the iteration order of the map
+    #[inline]
+    fn deterministic(&self) -> bool {
+        false
+    }
 }
 
 #[allow(clippy::len_without_is_empty)]
@@ -76,7 +112,43 @@ pub trait Decode {
     fn position(&self) -> usize;
     fn advance(&mut self, size: usize);
     fn eof(&self) -> bool;
-    fn sub_decoder(&mut self, size: usize) -> impl Decode;
+    /// decoder over the next `size` bytes, fails if the buffer is shorter
+    fn sub_decoder(&mut self, size: usize) -> Result<impl Decode, ProtoError>;
+
+    /// limits of the decoder, nested decoders share the limits of their parent
+    #[inline]
+    fn options(&self) -> DecodeOptions {
+        DecodeOptions::new()
+    }
+
+    /// number of messages and groups enclosing the current position, limited by
+    /// [DecodeOptions::max_depth]. decoders without depth tracking stay at `0`
+    #[inline]
+    fn depth(&self) -> usize {
+        0
+    }
+
+    /// see [Decode::depth], updated by [Decode::nested]
+    #[inline]
+    fn set_depth(&mut self, _depth: usize) {}
+
+    /// decode a nested message or group one level deeper, fails if the nesting exceeds
+    /// [DecodeOptions::max_depth]
+    #[inline]
+    fn nested<T>(
+        &mut self,
+        decode: impl FnOnce(&mut Self) -> Result<T, ProtoError>,
+    ) -> Result<T, ProtoError>
+    where
+        Self: Sized,
+    {
+        let depth = self.depth();
+        self.options().check_depth(depth + 1)?;
+        self.set_depth(depth + 1);
+        let result = decode(self);
+        self.set_depth(depth);
+        result
+    }
 
     #[inline]
     fn decode_int32(&mut self) -> Result<i32, ProtoError> {
@@ -108,6 +180,24 @@ pub trait Decode {
     fn decode_bytes(&mut self) -> Result<Vec<u8>, ProtoError>;
     fn decode_string(&mut self) -> Result<String, ProtoError>;
 
+    /// decode a string replacing invalid UTF-8 sequences with `U+FFFD`
+    #[inline]
+    fn decode_string_lossy(&mut self) -> Result<String, ProtoError> {
+        let bytes = self.decode_bytes()?;
+        Ok(match String::from_utf8(bytes) {
+            Ok(string) => string,
+            Err(err) => String::from_utf8_lossy(err.as_bytes()).into_owned(),
+        })
+    }
+
+    /// decode a length encoded block into [bytes::Bytes]. the default implementation copies, while
+    /// [BytesDecoder](crate::decoder::BytesDecoder) slices its buffer
+    #[cfg(feature = "bytes")]
+    #[inline]
+    fn decode_shared_bytes(&mut self) -> Result<bytes::Bytes, ProtoError> {
+        Ok(bytes::Bytes::from(self.decode_bytes()?))
+    }
+
     #[inline]
     fn decode_bool(&mut self) -> Result<bool, ProtoError> {
         Ok(self.decode_uint32()? != 0)
@@ -117,6 +207,77 @@ pub trait Decode {
     fn decode_tag(&mut self) -> Result<Tag, ProtoError> {
         Ok(Tag::from(self.decode_uint32()?))
     }
+
+    /// skip the value of a field which is not known to the message, groups are skipped including
+    /// their end group tag
+    fn skip_field(&mut self, tag: Tag) -> Result<(), ProtoError>
+    where
+        Self: Sized,
+    {
+        let size = match tag.wire_type() {
+            WIRE_TYPE_VARINT => {
+                self.decode_uint64()?;
+                return Ok(());
+            }
+            WIRE_TYPE_I64 => std::mem::size_of::<u64>(),
+            WIRE_TYPE_LENGTH_ENCODED => self.decode_uint32()? as usize,
+            WIRE_TYPE_I32 => std::mem::size_of::<u32>(),
+            WIRE_TYPE_START_GROUP => {
+                return self.nested(|decoder| {
+                    loop {
+                        if decoder.eof() {
+                            return Err(ProtoError::UnexpectedEof);
+                        }
+                        let inner = decoder.decode_tag()?;
+                        if inner.wire_type() == WIRE_TYPE_END_GROUP
+                            && inner.field_number() == tag.field_number()
+                        {
+                            return Ok(());
+                        }
+                        decoder.skip_field(inner)?;
+                    }
+                });
+            }
+            WIRE_TYPE_END_GROUP => return Err(ProtoError::UnexpectedEndGroup(tag.field_number())),
+            wire_type => return Err(ProtoError::UnknownWireType(wire_type)),
+        };
+
+        if self.buffer().len() < size {
+            return Err(ProtoError::UnexpectedEof);
+        }
+        self.advance(size);
+        Ok(())
+    }
+
+    /// decode a group, its fields are decoded as message up to the matching end group tag
+    fn decode_group<T: Message>(&mut self, field_number: u32) -> Result<T, ProtoError>
+    where
+        Self: Sized,
+    {
+        self.nested(|decoder| {
+            // the group has no length, its fields end at the end group tag
+            let mut group = GroupDecoder::new(decoder, field_number);
+            let message = T::decode_message(&mut group)?;
+            group.finish()?;
+            Ok(message)
+        })
+    }
+
+    /// merge a group into an existing message, see [Message::merge_message]
+    fn merge_group<T: Message>(
+        &mut self,
+        field_number: u32,
+        message: &mut T,
+    ) -> Result<(), ProtoError>
+    where
+        Self: Sized,
+    {
+        self.nested(|decoder| {
+            let mut group = GroupDecoder::new(decoder, field_number);
+            message.merge_message(&mut group)?;
+            group.finish()
+        })
+    }
 }
 
 pub trait Message {
@@ -128,9 +289,51 @@ pub trait Message {
 
     fn encode_message(&self, encoder: &mut impl Encode);
 
+    /// encode into a new buffer using deterministic encoding, see [Encode::deterministic]
+    fn encode_deterministic(&self) -> Vec<u8> {
+        let mut buffer = vec![0u8; self.message_size_hint()];
+        let mut encoder = Encoder::new_deterministic(&mut buffer);
+        self.encode_message(&mut encoder);
+        buffer
+    }
+
     fn decode_message(decoder: &mut impl Decode) -> Result<Self, ProtoError>
     where
         Self: Sized;
+
+    /// decode fields into an existing message: scalars take the last value, nested messages merge
+    /// and repeated fields append. the default implementation replaces the message
+    fn merge_message(&mut self, decoder: &mut impl Decode) -> Result<(), ProtoError>
+    where
+        Self: Sized,
+    {
+        *self = Self::decode_message(decoder)?;
+        Ok(())
+    }
+
+    fn length_delimited_size_hint(&self) -> usize {
+        let size = self.message_size_hint();
+        let mut hint = SizeHint::default();
+        hint.encode_uint64(size as u64);
+        hint.size() + size
+    }
+
+    /// encode with the size of the message prepended as varint, the framing used by
+    /// `writeDelimitedTo` of the Java implementation
+    fn encode_length_delimited(&self, encoder: &mut impl Encode) {
+        encoder.encode_uint64(self.message_size_hint() as u64);
+        self.encode_message(encoder);
+    }
+
+    /// decode a message prefixed by its size, see [Message::encode_length_delimited]
+    fn decode_length_delimited(decoder: &mut impl Decode) -> Result<Self, ProtoError>
+    where
+        Self: Sized,
+    {
+        let size = decoder.decode_uint32()? as usize;
+        decoder.options().check_message_size(size)?;
+        Self::decode_message(&mut decoder.sub_decoder(size)?)
+    }
 }
 
 pub trait PackableMarker<ProtobufType> {}
@@ -160,6 +363,8 @@ pub trait Unpacked<ProtobufType> {
         hint.size()
     }
 
+    /// every element is encoded with its own tag, only the field number of `tag` is used and the
+    /// wire type is the one of the element
     fn encode(&self, tag: Tag, encoder: &mut impl Encode);
 }
 
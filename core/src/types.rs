@@ -1,5 +1,8 @@
 mod ipv4addr;
 
+#[cfg(feature = "bytes")]
+mod bytes;
+
 #[cfg(feature = "uuid")]
 mod uuid;
 
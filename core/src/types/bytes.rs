@@ -0,0 +1,61 @@
+use crate::WIRE_TYPE_LENGTH_ENCODED;
+
+impl crate::Scalar<crate::scalars::Bytes> for ::bytes::Bytes {
+    const WIRE_TYPE: u8 = WIRE_TYPE_LENGTH_ENCODED;
+
+    fn encode(&self, encoder: &mut impl crate::Encode) {
+        encoder.encode_bytes(self);
+    }
+
+    fn decode(decoder: &mut impl crate::Decode) -> Result<Self, crate::ProtoError>
+    where
+        Self: Sized,
+    {
+        decoder.decode_shared_bytes()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        Scalar,
+        decoder::{BytesDecoder, Decoder},
+        encoder::Encoder,
+    };
+
+    #[test]
+    fn encode_decode() {
+        let input = ::bytes::Bytes::from_static(b"Hello World!");
+
+        let size_hint = <::bytes::Bytes as Scalar<crate::scalars::Bytes>>::size_hint(&input);
+        assert_eq!(size_hint, 13);
+
+        let mut buffer = vec![0u8; size_hint];
+        let mut encoder = Encoder::new(&mut buffer);
+        <::bytes::Bytes as Scalar<crate::scalars::Bytes>>::encode(&input, &mut encoder);
+
+        assert_eq!(
+            &buffer,
+            b"\x0c\x48\x65\x6c\x6c\x6f\x20\x57\x6f\x72\x6c\x64\x21"
+        );
+
+        let mut decoder = Decoder::new(&buffer);
+        let output =
+            <::bytes::Bytes as Scalar<crate::scalars::Bytes>>::decode(&mut decoder).unwrap();
+
+        assert_eq!(input, output)
+    }
+
+    #[test]
+    fn decode_zero_copy() {
+        let buffer =
+            ::bytes::Bytes::from_static(b"\x0c\x48\x65\x6c\x6c\x6f\x20\x57\x6f\x72\x6c\x64\x21");
+
+        let mut decoder = BytesDecoder::new(buffer.clone());
+        let output =
+            <::bytes::Bytes as Scalar<crate::scalars::Bytes>>::decode(&mut decoder).unwrap();
+
+        assert_eq!(&output[..], b"Hello World!");
+        assert_eq!(output.as_ptr(), buffer[1..].as_ptr());
+    }
+}
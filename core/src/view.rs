@@ -0,0 +1,204 @@
+use crate::{
+    Decode, ProtoError, Scalar, Tag, WIRE_TYPE_END_GROUP, WIRE_TYPE_LENGTH_ENCODED,
+    decoder::Decoder,
+};
+
+/// non-allocating view over an encoded message, fields are only decoded when accessed
+///
+/// ```
+/// use gin_tonic_core::{RawMessageView, scalars::ProtoString};
+///
+/// let view = RawMessageView::try_from(&b"\x0a\x05hello\x10\x01"[..]).unwrap();
+/// let name: Option<String> = view.get::<ProtoString, _>(1).unwrap();
+/// assert_eq!(name.as_deref(), Some("hello"));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct RawMessageView<'buf> {
+    buffer: &'buf [u8],
+}
+
+impl<'buf> TryFrom<&'buf [u8]> for RawMessageView<'buf> {
+    type Error = ProtoError;
+
+    /// checks that the buffer consists of complete fields
+    fn try_from(buffer: &'buf [u8]) -> Result<Self, Self::Error> {
+        let mut decoder = Decoder::new(buffer);
+        while !decoder.eof() {
+            let tag = decoder.decode_tag()?;
+            if tag.wire_type() == WIRE_TYPE_END_GROUP {
+                return Err(ProtoError::UnexpectedEndGroup(tag.field_number()));
+            }
+            decoder.skip_field(tag)?;
+        }
+        Ok(Self { buffer })
+    }
+}
+
+impl<'buf> RawMessageView<'buf> {
+    /// the encoded message
+    pub fn buffer(&self) -> &'buf [u8] {
+        self.buffer
+    }
+
+    /// fields in wire order, the raw value is everything following the tag, e.g. including the
+    /// length of length encoded fields and the end group tag of groups
+    pub fn iter(&self) -> RawFields<'buf> {
+        RawFields {
+            buffer: self.buffer,
+        }
+    }
+
+    /// decode a singular field, the last occurrence wins. occurrences with another wire type are
+    /// ignored
+    pub fn get<ProtobufType, T>(&self, field_number: u32) -> Result<Option<T>, ProtoError>
+    where
+        T: Scalar<ProtobufType>,
+    {
+        self.iter()
+            .filter(|(tag, _)| {
+                tag.field_number() == field_number && tag.wire_type() == T::WIRE_TYPE
+            })
+            .last()
+            .map(|(_, value)| T::decode(&mut Decoder::new(value)))
+            .transpose()
+    }
+
+    /// decode all values of a repeated field, packed or not
+    pub fn get_repeated<ProtobufType, T>(&self, field_number: u32) -> Result<Vec<T>, ProtoError>
+    where
+        T: Scalar<ProtobufType>,
+    {
+        let mut values = Vec::new();
+        for (tag, value) in self.iter() {
+            if tag.field_number() != field_number {
+                continue;
+            }
+
+            let mut decoder = Decoder::new(value);
+            if tag.wire_type() == T::WIRE_TYPE {
+                values.push(T::decode(&mut decoder)?);
+            } else if tag.wire_type() == WIRE_TYPE_LENGTH_ENCODED {
+                let size = decoder.decode_uint32()? as usize;
+                let mut packed = decoder.sub_decoder(size)?;
+                while !packed.eof() {
+                    values.push(T::decode(&mut packed)?);
+                }
+            }
+        }
+        Ok(values)
+    }
+
+    /// view of a nested message, the last occurrence wins
+    pub fn message(&self, field_number: u32) -> Result<Option<RawMessageView<'buf>>, ProtoError> {
+        let Some((_, value)) = self
+            .iter()
+            .filter(|(tag, _)| {
+                tag.field_number() == field_number && tag.wire_type() == WIRE_TYPE_LENGTH_ENCODED
+            })
+            .last()
+        else {
+            return Ok(None);
+        };
+
+        let mut decoder = Decoder::new(value);
+        decoder.decode_uint32()?;
+        RawMessageView::try_from(decoder.buffer()).map(Some)
+    }
+}
+
+impl<'buf> IntoIterator for RawMessageView<'buf> {
+    type Item = (Tag, &'buf [u8]);
+    type IntoIter = RawFields<'buf>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// fields of a [RawMessageView]
+#[derive(Clone, Debug)]
+pub struct RawFields<'buf> {
+    buffer: &'buf [u8],
+}
+
+impl<'buf> Iterator for RawFields<'buf> {
+    type Item = (Tag, &'buf [u8]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // the view was checked when created, malformed data ends the iteration
+        let mut decoder = Decoder::new(self.buffer);
+        if decoder.eof() {
+            return None;
+        }
+        let tag = decoder.decode_tag().ok()?;
+        let start = decoder.position();
+        decoder.skip_field(tag).ok()?;
+
+        let value = &self.buffer[start..decoder.position()];
+        self.buffer = decoder.buffer();
+        Some((tag, value))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        WIRE_TYPE_START_GROUP, WIRE_TYPE_VARINT,
+        scalars::{Int32, ProtoString, UInt32},
+    };
+
+    use super::*;
+
+    #[test]
+    fn iterate() {
+        let buffer = b"\x08\x96\x01\x12\x02hi\x1b\x08\x01\x1c";
+        let view = RawMessageView::try_from(&buffer[..]).unwrap();
+
+        let fields = view.iter().collect::<Vec<_>>();
+        assert_eq!(
+            fields,
+            [
+                (Tag::from_parts(1, WIRE_TYPE_VARINT), &b"\x96\x01"[..]),
+                (Tag::from_parts(2, WIRE_TYPE_LENGTH_ENCODED), &b"\x02hi"[..]),
+                (
+                    Tag::from_parts(3, WIRE_TYPE_START_GROUP),
+                    &b"\x08\x01\x1c"[..]
+                ),
+            ]
+        );
+        assert_eq!(view.into_iter().count(), 3);
+    }
+
+    #[test]
+    fn lazy_access() {
+        // header { id = 7 } name = "a" name = "b" ids = [1, 2] packed, ids = 3
+        let buffer = b"\x0a\x02\x08\x07\x12\x01a\x12\x01b\x1a\x02\x01\x02\x18\x03";
+        let view = RawMessageView::try_from(&buffer[..]).unwrap();
+
+        let header = view.message(1).unwrap().unwrap();
+        assert_eq!(header.get::<UInt32, u32>(1).unwrap(), Some(7));
+        assert_eq!(
+            view.get::<ProtoString, String>(2).unwrap(),
+            Some(String::from("b"))
+        );
+        assert_eq!(view.get_repeated::<Int32, i32>(3).unwrap(), vec![1, 2, 3]);
+
+        assert_eq!(view.get::<Int32, i32>(4).unwrap(), None);
+        assert!(view.message(4).unwrap().is_none());
+    }
+
+    #[test]
+    fn malformed() {
+        assert!(matches!(
+            RawMessageView::try_from(&b"\x0a\x05hi"[..]),
+            Err(ProtoError::UnexpectedEof)
+        ));
+        assert!(matches!(
+            RawMessageView::try_from(&b"\x0c"[..]),
+            Err(ProtoError::UnexpectedEndGroup(1))
+        ));
+        // a nested message is checked when accessed
+        let view = RawMessageView::try_from(&b"\x0a\x02\x0a\x05"[..]).unwrap();
+        assert!(view.message(1).is_err());
+    }
+}
@@ -1,4 +1,6 @@
 pub const WIRE_TYPE_VARINT: u8 = 0;
 pub const WIRE_TYPE_I64: u8 = 1;
 pub const WIRE_TYPE_LENGTH_ENCODED: u8 = 2;
+pub const WIRE_TYPE_START_GROUP: u8 = 3;
+pub const WIRE_TYPE_END_GROUP: u8 = 4;
 pub const WIRE_TYPE_I32: u8 = 5;
@@ -2,17 +2,20 @@ use darling::ast::Data;
 use darling::util::Flag;
 use darling::{FromDeriveInput, FromField, FromMeta, FromVariant};
 use proc_macro2::TokenStream;
-use quote::quote;
-use syn::{GenericArgument, Ident, LitBool, LitInt, PathArguments, PathSegment, Type};
+use quote::{ToTokens, quote};
+use syn::{GenericArgument, Ident, LitBool, LitInt, LitStr, PathArguments, PathSegment, Type};
 
 pub(crate) type MessageDeriveData = Data<(), MessageField>;
 
 #[derive(Debug, FromDeriveInput)]
-#[darling(attributes(gin), supports(struct_named))]
+#[darling(attributes(gin), supports(struct_named, struct_tuple, struct_newtype))]
 pub(crate) struct MessageInput {
     pub(crate) ident: Ident,
+    pub(crate) generics: syn::Generics,
     pub(crate) data: MessageDeriveData,
     pub(crate) root: Option<syn::Path>,
+    /// the single field is encoded in place of the struct, deriving `Scalar` instead of `Message`
+    pub(crate) transparent: Flag,
 }
 
 #[derive(Clone, Debug, FromField)]
@@ -22,12 +25,20 @@ pub(crate) struct MessageField {
     pub(crate) ident: Option<Ident>,
     pub(crate) ty: Type,
 
-    pub(crate) id: LitInt,
+    /// required unless the struct is transparent
+    pub(crate) id: Option<LitInt>,
     pub(crate) oneof: Flag,
+    /// `ExtensionSet` keeping extensions and unknown fields, doesn't take a field number
+    pub(crate) extensions: Flag,
+    /// encode the message as proto2 group instead of a length encoded field
+    pub(crate) group: Flag,
     pub(crate) packed: Option<LitBool>,
     pub(crate) scalar: Option<Scalar>,
     pub(crate) key_scalar: Option<Scalar>,
     pub(crate) value_scalar: Option<Scalar>,
+    /// value used when the field is absent on decode instead of failing with `MissingField`
+    pub(crate) default: Option<syn::Expr>,
+    pub(crate) utf8: Option<Utf8>,
 }
 
 #[derive(Clone, Copy, Debug, FromMeta)]
@@ -47,6 +58,48 @@ pub(crate) enum Scalar {
     String,
     Bool,
     Bytes,
+    /// selected by `utf8 = "lossy"`
+    #[darling(skip)]
+    LossyString,
+}
+
+/// validation of strings on decode
+#[derive(Clone, Copy, Debug, FromMeta)]
+pub(crate) enum Utf8 {
+    Strict,
+    Lossy,
+    /// the raw bytes are kept, requires a bytes type like `Vec<u8>`
+    None,
+}
+
+impl Utf8 {
+    /// the scalar of a field of type `ty`, `Err` if the mode does not apply to the field
+    pub(crate) fn apply(
+        self,
+        scalar: Option<Scalar>,
+        ty: &Type,
+    ) -> Result<Option<Scalar>, TokenStream> {
+        let string_scalar = match scalar {
+            Some(scalar) => matches!(scalar, Scalar::String),
+            None => is_string(ty),
+        };
+
+        match self {
+            Utf8::Strict if string_scalar => Ok(scalar),
+            Utf8::Lossy if string_scalar => Ok(Some(Scalar::LossyString)),
+            Utf8::None if is_string(ty) => Err(compile_error(
+                ty,
+                "`utf8 = \"none\"` keeps the raw bytes, use a bytes type like `Vec<u8>`",
+            )),
+            Utf8::None if matches!(scalar, None | Some(Scalar::String | Scalar::Bytes)) => {
+                Ok(Some(Scalar::Bytes))
+            }
+            _ => Err(compile_error(
+                ty,
+                "`utf8` is only supported on string fields",
+            )),
+        }
+    }
 }
 
 pub(crate) trait ScalarToken {
@@ -64,10 +117,20 @@ impl ScalarToken for syn::Type {
                         let text = ident.to_string();
 
                         match text.as_str() {
-                            "i32" => quote!(compile_error!("i32 needs scalar type")),
-                            "i64" => quote!(compile_error!("i64 needs scalar type")),
-                            "u32" => quote!(compile_error!("u32 needs scalar type")),
-                            "u64" => quote!(compile_error!("u64 needs scalar type")),
+                            "i32" => compile_error(
+                                self,
+                                "i32 needs a scalar type: int32, sint32 or sfixed32",
+                            ),
+                            "i64" => compile_error(
+                                self,
+                                "i64 needs a scalar type: int64, sint64 or sfixed64",
+                            ),
+                            "u32" => {
+                                compile_error(self, "u32 needs a scalar type: uint32 or fixed32")
+                            }
+                            "u64" => {
+                                compile_error(self, "u64 needs a scalar type: uint64 or fixed64")
+                            }
                             "String" => quote! { #root::scalars::ProtoString },
                             "bool" => quote! { #root::scalars::Bool },
                             "f32" => quote! { #root::scalars::Float },
@@ -80,11 +143,34 @@ impl ScalarToken for syn::Type {
                     }
                 }
             }
-            _ => todo!("impl ScalarToken for syn::Type = {self:?}"),
+            Type::Array(array) if is_u8(&array.elem) => quote! { #root::scalars::Bytes },
+            Type::Group(group) => group.elem.scalar_token(root),
+            Type::Paren(paren) => paren.elem.scalar_token(root),
+            Type::Reference(_) => compile_error(
+                self,
+                "references are not supported, use an owned type like `String` or `Vec<u8>`",
+            ),
+            _ => compile_error(
+                self,
+                "unsupported field type, expected a type path like `String`, `Vec<T>` or `Option<T>`, or a byte array `[u8; N]`",
+            ),
         }
     }
 }
 
+fn is_string(ty: &syn::Type) -> bool {
+    matches!(ty, Type::Path(type_path) if type_path.path.is_ident("String"))
+}
+
+fn is_u8(ty: &syn::Type) -> bool {
+    matches!(ty, Type::Path(type_path) if type_path.path.is_ident("u8"))
+}
+
+/// a `compile_error!` pointing at the given tokens
+pub(crate) fn compile_error(tokens: impl ToTokens, message: &str) -> TokenStream {
+    syn::Error::new_spanned(tokens, message).into_compile_error()
+}
+
 impl ScalarToken for Scalar {
     fn scalar_token(&self, root: &proc_macro2::TokenStream) -> TokenStream {
         match self {
@@ -103,6 +189,7 @@ impl ScalarToken for Scalar {
             Scalar::String => quote! { #root::scalars::ProtoString },
             Scalar::Bool => quote! { #root::scalars::Bool },
             Scalar::Bytes => quote! { #root::scalars::Bytes },
+            Scalar::LossyString => quote! { #root::scalars::LossyString },
         }
     }
 }
@@ -156,6 +243,11 @@ impl IsRepeated for syn::Type {
                 if let PathArguments::AngleBracketed(args) = &segment.arguments {
                     // We expect exactly one generic argument: the inner type
                     if let Some(GenericArgument::Type(inner_type)) = args.args.first() {
+                        // Vec<u8> is a bytes scalar, not a repeated field
+                        if is_u8(inner_type) {
+                            return None;
+                        }
+
                         return Some(inner_type);
                     }
                 }
@@ -178,12 +270,16 @@ impl IsMap for syn::Type {
                 // Get the last segment (e.g., 'HashMap' in 'std::option::Option')
                 let segment: &PathSegment = type_path.path.segments.last()?;
 
-                if segment.ident != "FxHashMap" {
+                if !matches!(
+                    segment.ident.to_string().as_str(),
+                    "FxHashMap" | "HashMap" | "BTreeMap" | "IndexMap"
+                ) {
                     return None;
                 }
 
                 if let PathArguments::AngleBracketed(args) = &segment.arguments {
-                    // HashMap requires two type arguments: HashMap<K, V>
+                    // maps require at least two type arguments: HashMap<K, V>, an optional third
+                    // one being the hasher
                     let mut types = args.args.iter().filter_map(|arg| {
                         if let GenericArgument::Type(inner_type) = arg {
                             Some(inner_type)
@@ -211,7 +307,7 @@ pub(crate) trait IsPackable {
 
 impl IsPackable for Scalar {
     fn is_packable(&self) -> bool {
-        !matches!(self, Scalar::Bytes | Scalar::String)
+        !matches!(self, Scalar::Bytes | Scalar::String | Scalar::LossyString)
     }
 }
 
@@ -251,13 +347,26 @@ pub(crate) struct EnumerationInput {
     pub(crate) ident: Ident,
     pub(crate) data: EnumDeriveData,
     pub(crate) root: Option<syn::Path>,
+    #[darling(default)]
+    pub(crate) repr: Repr,
+}
+
+/// protobuf representation of an enumeration
+#[derive(Clone, Copy, Debug, Default, FromMeta)]
+pub(crate) enum Repr {
+    #[default]
+    Int32,
+    String,
 }
 
 #[derive(Clone, Debug, FromVariant)]
 #[darling(attributes(gin))]
 pub(crate) struct EnumVariant {
     pub(crate) ident: Ident,
-    pub(crate) id: LitInt,
+    /// required unless the enumeration is represented as string
+    pub(crate) id: Option<LitInt>,
+    /// the proto value name, defaults to the variant ident
+    pub(crate) name: Option<LitStr>,
 }
 
 pub(crate) type OneOfDeriveData = Data<OneOfVariant, ()>;
@@ -279,4 +388,5 @@ pub(crate) struct OneOfVariant {
 
     pub(crate) id: LitInt,
     pub(crate) scalar: Option<Scalar>,
+    pub(crate) utf8: Option<Utf8>,
 }
@@ -1,10 +1,11 @@
 use darling::ast::Fields;
-use proc_macro2::TokenStream;
-use quote::{format_ident, quote, quote_spanned};
-use syn::Ident;
+use proc_macro2::{TokenStream, TokenTree};
+use quote::{ToTokens, format_ident, quote_spanned};
+use syn::{Generics, Ident, parse_quote, spanned::Spanned};
 
 use crate::ast::{
-    IsMap, IsOption, IsPackable, IsRepeated, MessageDeriveData, MessageField, ScalarToken,
+    IsMap, IsOption, IsPackable, IsRepeated, MessageDeriveData, MessageField, Repr, ScalarToken,
+    compile_error,
 };
 
 pub(crate) fn expand_message(
@@ -12,18 +13,118 @@ pub(crate) fn expand_message(
     input: crate::ast::MessageInput,
 ) -> TokenStream {
     let ty = input.ident;
+    let generics = input.generics;
 
     match input.data {
-        MessageDeriveData::Enum(_) => quote! { compile_error!("enum_tuple not supported") },
-        MessageDeriveData::Struct(fields) => expand_struct_message(root, ty, fields),
+        MessageDeriveData::Enum(_) => compile_error(
+            &ty,
+            "Message can only be derived for structs, use OneOf for enums",
+        ),
+        MessageDeriveData::Struct(fields) if input.transparent.is_present() => {
+            expand_transparent(root, ty, generics, fields)
+        }
+        MessageDeriveData::Struct(fields) => expand_struct_message(root, ty, generics, fields),
+    }
+}
+
+/// newtypes delegating [Scalar] and [PackableMarker] to the wrapped field for every protobuf type
+fn expand_transparent(
+    root: &proc_macro2::TokenStream,
+    ty: Ident,
+    generics: Generics,
+    fields: Fields<MessageField>,
+) -> TokenStream {
+    let span = ty.span();
+
+    let [field] = fields.fields.as_slice() else {
+        return compile_error(&ty, "transparent structs must have exactly one field");
+    };
+    let inner = &field.ty;
+    let decode = quote_spanned! {span=> <#inner as #root::Scalar<ProtobufType>>::decode(decoder)? };
+    let (member, construct) = match &field.ident {
+        Some(ident) => (
+            syn::Member::Named(ident.clone()),
+            quote_spanned! {span=> Self { #ident: #decode } },
+        ),
+        None => (
+            syn::Member::Unnamed(syn::Index::from(0)),
+            quote_spanned! {span=> Self(#decode) },
+        ),
+    };
+
+    let (_, ty_generics, _) = generics.split_for_impl();
+
+    let mut marker_generics = generics.clone();
+    marker_generics.params.push(parse_quote!(ProtobufType));
+    let mut scalar_generics = marker_generics.clone();
+
+    marker_generics
+        .make_where_clause()
+        .predicates
+        .push(parse_quote!(#inner: #root::PackableMarker<ProtobufType>));
+    let (marker_impl_generics, _, marker_where_clause) = marker_generics.split_for_impl();
+
+    scalar_generics
+        .make_where_clause()
+        .predicates
+        .push(parse_quote!(#inner: #root::Scalar<ProtobufType>));
+    let (scalar_impl_generics, _, scalar_where_clause) = scalar_generics.split_for_impl();
+
+    quote_spanned! {span=>
+        #[automatically_derived]
+        impl #marker_impl_generics #root::PackableMarker<ProtobufType> for #ty #ty_generics
+        #marker_where_clause
+        {}
+
+        #[automatically_derived]
+        impl #scalar_impl_generics #root::Scalar<ProtobufType> for #ty #ty_generics
+        #scalar_where_clause
+        {
+            const WIRE_TYPE: u8 = <#inner as #root::Scalar<ProtobufType>>::WIRE_TYPE;
+
+            #[inline]
+            fn encode(&self, encoder: &mut impl #root::Encode) {
+                <#inner as #root::Scalar<ProtobufType>>::encode(&self.#member, encoder)
+            }
+
+            #[inline]
+            fn decode(decoder: &mut impl #root::Decode) -> Result<Self, #root::ProtoError>
+            where
+                Self: Sized,
+            {
+                Ok(#construct)
+            }
+
+            #[inline]
+            fn merge(&mut self, decoder: &mut impl #root::Decode) -> Result<(), #root::ProtoError>
+            where
+                Self: Sized,
+            {
+                <#inner as #root::Scalar<ProtobufType>>::merge(&mut self.#member, decoder)
+            }
+        }
     }
 }
+
 fn expand_struct_message(
     root: &proc_macro2::TokenStream,
     ty: Ident,
+    generics: Generics,
     fields: Fields<MessageField>,
 ) -> TokenStream {
     let span = ty.span();
+    let is_tuple = fields.style.is_tuple();
+
+    // bounds for fields using type parameters, added to the impls of the message and builder
+    let mut bounded_generics = generics.clone();
+    let mut add_bound = |field_ty: &syn::Type, bound: TokenStream| {
+        if mentions_type_param(field_ty, &generics) {
+            bounded_generics
+                .make_where_clause()
+                .predicates
+                .push(parse_quote!(#field_ty: #bound));
+        }
+    };
 
     let mut encode_impl = TokenStream::new();
 
@@ -33,17 +134,206 @@ fn expand_struct_message(
     let mut builder_destructuring = TokenStream::new();
     let mut finish_field = TokenStream::new();
     let mut decode_field = TokenStream::new();
+    // decoding into an existing message, see `Message::merge_message`
+    let mut merge_field = TokenStream::new();
 
     let mut oneof_match = TokenStream::new();
+    let mut merge_oneof_match = TokenStream::new();
+
+    // field names by field number, used as path of decoding errors
+    let mut field_names = TokenStream::new();
 
-    for field in fields {
+    // fields without a match are skipped unless the message keeps them
+    let mut unknown_field = quote::quote! { decoder.skip_field(tag)? };
+    let mut merge_unknown_field = unknown_field.clone();
+
+    for (index, field) in fields.into_iter().enumerate() {
         let id = field.id;
         let ty = field.ty;
-        let field_ident = field
-            .ident
-            .clone()
-            .expect("named struct fields have idents");
-        let span = field_ident.span();
+        // tuple struct fields are accessed by index and named `field_N` in the builder
+        let (member, field_ident) = match field.ident {
+            Some(ident) => (syn::Member::Named(ident.clone()), ident),
+            None => (
+                syn::Member::Unnamed(syn::Index::from(index)),
+                format_ident!("field_{index}"),
+            ),
+        };
+        let field_name = match &member {
+            syn::Member::Named(ident) => ident.to_string(),
+            syn::Member::Unnamed(index) => index.index.to_string(),
+        };
+        let span = ty.span();
+        // tuple structs are constructed positionally
+        let init = match member {
+            syn::Member::Named(_) => quote_spanned! { span=> #member: },
+            syn::Member::Unnamed(_) => TokenStream::new(),
+        };
+
+        if field.extensions.is_present() {
+            builder_fields.extend(quote_spanned! { span=>
+                #field_ident: #ty,
+            });
+            builder_new.extend(quote_spanned! { span=>
+                #field_ident: Default::default(),
+            });
+            builder_destructuring.extend(quote_spanned! { span=>
+                #field_ident,
+            });
+            finish_field.extend(quote_spanned! { span=>
+                #field_ident,
+            });
+            encode_impl.extend(quote_spanned! { span=>
+                #root::ExtensionSet::encode(&self.#member, encoder);
+            });
+            unknown_field = quote_spanned! { span=>
+                #root::ExtensionSet::decode_field(&mut self.#field_ident, tag, decoder)?
+            };
+            merge_unknown_field = quote_spanned! { span=>
+                #root::ExtensionSet::decode_field(&mut message.#member, tag, decoder)?
+            };
+            continue;
+        }
+
+        let Some(id) = id else {
+            return compile_error(&ty, "missing field number, add `#[gin(id = N)]`");
+        };
+
+        if let Some(default) = &field.default
+            && (field.oneof.is_present()
+                || ty.is_option().is_some()
+                || ty.is_repeated().is_some()
+                || ty.is_map().is_some())
+        {
+            return compile_error(
+                default,
+                "`default` is only supported on non-optional scalar fields",
+            );
+        }
+
+        // `utf8` selects the scalar of string fields, for maps it applies to string keys and values
+        let (scalar, key_scalar, value_scalar) = match field.utf8 {
+            None => (field.scalar, field.key_scalar, field.value_scalar),
+            Some(_) if field.oneof.is_present() || field.group.is_present() => {
+                return compile_error(&ty, "`utf8` is only supported on string fields");
+            }
+            Some(utf8) => match ty.is_map() {
+                Some((key_ty, value_ty)) => {
+                    match (
+                        utf8.apply(field.key_scalar, key_ty),
+                        utf8.apply(field.value_scalar, value_ty),
+                    ) {
+                        (Err(err), Err(_)) => return err,
+                        (key, value) => (
+                            field.scalar,
+                            key.unwrap_or(field.key_scalar),
+                            value.unwrap_or(field.value_scalar),
+                        ),
+                    }
+                }
+                None => {
+                    let inner = ty.is_option().or_else(|| ty.is_repeated()).unwrap_or(&ty);
+                    match utf8.apply(field.scalar, inner) {
+                        Ok(scalar) => (scalar, field.key_scalar, field.value_scalar),
+                        Err(err) => return err,
+                    }
+                }
+            },
+        };
+
+        if field.oneof.is_present() {
+            field_names.extend(quote_spanned! { span=>
+                number if <#ty>::matches_tag(#root::Tag::from_parts(number, 0)) => Some(#field_name),
+            });
+        } else {
+            field_names.extend(quote_spanned! { span=>
+                #id => Some(#field_name),
+            });
+        }
+
+        if field.group.is_present() {
+            if let Some(inner) = ty.is_repeated() {
+                add_bound(inner, quote_spanned! { span=> #root::Message });
+
+                builder_fields.extend(quote_spanned! { span=>
+                    #field_ident: #ty,
+                });
+                builder_new.extend(quote_spanned! { span=>
+                    #field_ident: Vec::new(),
+                });
+                decode_field.extend(quote_spanned! { span=>
+                    #id => {
+                        decoder.options().check_repeated(self.#field_ident.len())?;
+                        self.#field_ident.push(decoder.decode_group(#id)?);
+                    }
+                });
+                merge_field.extend(quote_spanned! { span=>
+                    #id => {
+                        decoder.options().check_repeated(message.#member.len())?;
+                        message.#member.push(decoder.decode_group(#id)?);
+                    }
+                });
+                encode_impl.extend(quote_spanned! { span=>
+                    for value in &self.#member {
+                        encoder.encode_group(#id, value);
+                    }
+                });
+            } else if let Some(inner) = ty.is_option() {
+                add_bound(inner, quote_spanned! { span=> #root::Message });
+
+                builder_fields.extend(quote_spanned! { span=>
+                    #field_ident: #ty,
+                });
+                builder_new.extend(quote_spanned! { span=>
+                    #field_ident: None,
+                });
+                let decode = merge_group(&quote_spanned! { span=> self.#field_ident }, &id);
+                decode_field.extend(quote_spanned! { span=>
+                    #id => #decode,
+                });
+                let merge = merge_group(&quote_spanned! { span=> message.#member }, &id);
+                merge_field.extend(quote_spanned! { span=>
+                    #id => #merge,
+                });
+                encode_impl.extend(quote_spanned! { span=>
+                    if let Some(value) = &self.#member {
+                        encoder.encode_group(#id, value);
+                    }
+                });
+            } else {
+                add_bound(&ty, quote_spanned! { span=> #root::Message });
+
+                builder_fields.extend(quote_spanned! { span=>
+                    #field_ident: Option<#ty>,
+                });
+                builder_new.extend(quote_spanned! { span=>
+                    #field_ident: None,
+                });
+                let decode = merge_group(&quote_spanned! { span=> self.#field_ident }, &id);
+                decode_field.extend(quote_spanned! { span=>
+                    #id => #decode,
+                });
+                merge_field.extend(quote_spanned! { span=>
+                    #id => decoder.merge_group(#id, &mut message.#member)?,
+                });
+                encode_impl.extend(quote_spanned! { span=>
+                    encoder.encode_group(#id, &self.#member);
+                });
+            }
+
+            builder_destructuring.extend(quote_spanned! { span=>
+                #field_ident,
+            });
+            if ty.is_repeated().is_some() || ty.is_option().is_some() {
+                finish_field.extend(quote_spanned! { span=>
+                    #field_ident,
+                });
+            } else {
+                finish_field.extend(quote_spanned! { span=>
+                    #init #field_ident.ok_or(#root::gin_tonic_core::ProtoError::MissingField(#id))?,
+                });
+            }
+            continue;
+        }
 
         if field.oneof.is_present() {
             builder_fields.extend(quote_spanned! { span=>
@@ -56,25 +346,34 @@ fn expand_struct_message(
                 #field_ident,
             });
             finish_field.extend(quote_spanned! { span=>
-                #field_ident: #field_ident.ok_or(#root::gin_tonic_core::ProtoError::MissingField(#id))?,
+                #init #field_ident.ok_or(#root::gin_tonic_core::ProtoError::MissingField(#id))?,
             });
-            decode_field.extend(quote_spanned! { span=> });
 
             encode_impl.extend(quote_spanned! { span=>
-                self.#field_ident.encode_message(encoder);
+                self.#member.encode_message(encoder);
             });
 
             oneof_match = quote_spanned! { span=>
-                if #ty::matches_tag(tag) {
-                    self.#field_ident = Some(#ty::decode_field(tag, decoder)?);
+                if <#ty>::matches_tag(tag) {
+                    match &mut self.#field_ident {
+                        Some(value) => value.merge_field(tag, decoder)?,
+                        None => self.#field_ident = Some(<#ty>::decode_field(tag, decoder)?),
+                    }
+                    return Ok(());
+                }
+            };
+            merge_oneof_match = quote_spanned! { span=>
+                if <#ty>::matches_tag(tag) {
+                    message.#member.merge_field(tag, decoder)?;
                     return Ok(());
                 }
             };
         } else if let Some(inner) = ty.is_option() {
-            let scalar_ty = match field.scalar {
+            let scalar_ty = match scalar {
                 Some(scalar) => scalar.scalar_token(root),
                 None => inner.scalar_token(root),
             };
+            add_bound(inner, quote_spanned! { span=> #root::Scalar<#scalar_ty> });
 
             builder_fields.extend(quote_spanned! { span=>
                 #field_ident: #ty,
@@ -88,36 +387,45 @@ fn expand_struct_message(
             finish_field.extend(quote_spanned! { span=>
                 #field_ident,
             });
+            let decode = merge_option(
+                &quote_spanned! { span=> self.#field_ident },
+                inner,
+                &scalar_ty,
+            );
             decode_field.extend(quote_spanned! { span=>
-                #id => self.#field_ident = Some(<#inner as Scalar::<#scalar_ty>>::decode(decoder)?),
+                #id => #decode,
+            });
+            let merge = merge_option(
+                &quote_spanned! { span=> message.#member },
+                inner,
+                &scalar_ty,
+            );
+            merge_field.extend(quote_spanned! { span=>
+                #id => #merge,
             });
 
             encode_impl.extend(quote_spanned! { span=>
-                if let Some(value) = &self.#field_ident {
+                if let Some(value) = &self.#member {
                     <#inner as Scalar::<#scalar_ty>>::encode_field(value, #id, encoder);
                 }
             });
         } else if let Some(inner) = ty.is_repeated() {
-            let (scalar_ty, packed) = match field.scalar {
-                Some(scalar) => {
-                    let is_packable = scalar.is_packable();
-                    let packed = field
-                        .packed
-                        .map(|lit_bool| lit_bool.value)
-                        .unwrap_or(is_packable);
-                    let scalar_ty = scalar.scalar_token(root);
-                    (scalar_ty, packed)
-                }
-                None => {
-                    let is_packable = inner.is_packable();
-                    let packed = field
-                        .packed
-                        .map(|lit_bool| lit_bool.value)
-                        .unwrap_or(is_packable);
-                    let scalar_ty = inner.scalar_token(root);
-                    (scalar_ty, packed)
-                }
+            let (scalar_ty, is_packable) = match scalar {
+                Some(scalar) => (scalar.scalar_token(root), scalar.is_packable()),
+                None => (inner.scalar_token(root), inner.is_packable()),
             };
+            let packed = field
+                .packed
+                .map(|lit_bool| lit_bool.value)
+                .unwrap_or(is_packable);
+            if is_packable {
+                add_bound(
+                    inner,
+                    quote_spanned! { span=> #root::Scalar<#scalar_ty> + #root::PackableMarker<#scalar_ty> },
+                );
+            } else {
+                add_bound(inner, quote_spanned! { span=> #root::Scalar<#scalar_ty> });
+            }
 
             builder_fields.extend(quote_spanned! { span=>
                 #field_ident: #ty,
@@ -134,38 +442,62 @@ fn expand_struct_message(
 
             if packed {
                 encode_impl.extend(quote_spanned! { span=>
-                    <Vec<#inner> as #root::Packed::<#scalar_ty>>::encode(&self.#field_ident, #id, encoder);
-                });
-                decode_field.extend(quote_spanned! { span=>
-                    #id => <Vec<#inner> as #root::Packed<#scalar_ty>>::decode(decoder, &mut self.#field_ident)?,
+                    <Vec<#inner> as #root::Packed::<#scalar_ty>>::encode(&self.#member, #id, encoder);
                 });
             } else {
                 encode_impl.extend(quote_spanned! { span=>
                     <Vec<#inner> as #root::Unpacked::<#scalar_ty>>::encode(
-                        &self.#field_ident,
-                        #root::Tag::from_parts(#id, #root::WIRE_TYPE_LENGTH_ENCODED),
+                        &self.#member,
+                        #root::Tag::from_parts(#id, <#inner as #root::Scalar<#scalar_ty>>::WIRE_TYPE),
                         encoder,
                     );
                 });
-                decode_field.extend(quote_spanned! { span=>
-                    #id => self.#field_ident.push(<#inner as #root::Scalar<#scalar_ty>>::decode(decoder)?),
-                });
+            }
+
+            // parsers must accept both encodings of packable fields regardless of the own setting
+            for (target, fields) in [
+                (
+                    quote_spanned! { span=> self.#field_ident },
+                    &mut decode_field,
+                ),
+                (quote_spanned! { span=> message.#member }, &mut merge_field),
+            ] {
+                if is_packable {
+                    fields.extend(quote_spanned! { span=>
+                        #id if tag.wire_type() == #root::WIRE_TYPE_LENGTH_ENCODED => <Vec<#inner> as #root::Packed<#scalar_ty>>::decode(decoder, &mut #target)?,
+                        #id => {
+                            decoder.options().check_repeated(#target.len())?;
+                            #target.push(<#inner as #root::Scalar<#scalar_ty>>::decode(decoder)?);
+                        }
+                    });
+                } else {
+                    fields.extend(quote_spanned! { span=>
+                        #id => {
+                            decoder.options().check_repeated(#target.len())?;
+                            #target.push(<#inner as #root::Scalar<#scalar_ty>>::decode(decoder)?);
+                        }
+                    });
+                }
             }
         } else if let Some((key_ty, value_ty)) = ty.is_map() {
-            let key_scalar_ty = match field.key_scalar {
+            let key_scalar_ty = match key_scalar {
                 Some(scalar) => scalar.scalar_token(root),
                 None => key_ty.scalar_token(root),
             };
-            let value_scalar_ty = match field.value_scalar {
+            let value_scalar_ty = match value_scalar {
                 Some(scalar) => scalar.scalar_token(root),
                 None => value_ty.scalar_token(root),
             };
+            add_bound(
+                &ty,
+                quote_spanned! { span=> #root::Map<#key_scalar_ty, #value_scalar_ty> + Default },
+            );
 
             builder_fields.extend(quote_spanned! { span=>
                 #field_ident: #ty,
             });
             builder_new.extend(quote_spanned! { span=>
-                #field_ident: #root::fxhash::FxHashMap::with_capacity_and_hasher(8, Default::default()),
+                #field_ident: Default::default(),
             });
             builder_destructuring.extend(quote_spanned! { span=>
                 #field_ident,
@@ -176,15 +508,19 @@ fn expand_struct_message(
             decode_field.extend(quote_spanned! { span=>
                 #id => #root::Map::<#key_scalar_ty, #value_scalar_ty>::decode(decoder, &mut self.#field_ident)?,
             });
+            merge_field.extend(quote_spanned! { span=>
+                #id => #root::Map::<#key_scalar_ty, #value_scalar_ty>::decode(decoder, &mut message.#member)?,
+            });
 
             encode_impl.extend(quote_spanned! { span=>
-                #root::Map::<#key_scalar_ty, #value_scalar_ty>::encode(&self.#field_ident, #id, encoder);
+                #root::Map::<#key_scalar_ty, #value_scalar_ty>::encode(&self.#member, #id, encoder);
             });
         } else {
-            let scalar_ty = match field.scalar {
+            let scalar_ty = match scalar {
                 Some(scalar) => scalar.scalar_token(root),
                 None => ty.scalar_token(root),
             };
+            add_bound(&ty, quote_spanned! { span=> #root::Scalar<#scalar_ty> });
 
             builder_fields.extend(quote_spanned! { span=>
                 #field_ident: Option<#ty>,
@@ -195,23 +531,47 @@ fn expand_struct_message(
             builder_destructuring.extend(quote_spanned! { span=>
                 #field_ident,
             });
-            finish_field.extend(quote_spanned! { span=>
-                #field_ident: #field_ident.ok_or(#root::gin_tonic_core::ProtoError::MissingField(#id))?,
-            });
+            match &field.default {
+                Some(default) => finish_field.extend(quote_spanned! { span=>
+                    #init #field_ident.unwrap_or_else(|| #default),
+                }),
+                None => finish_field.extend(quote_spanned! { span=>
+                    #init #field_ident.ok_or(#root::gin_tonic_core::ProtoError::MissingField(#id))?,
+                }),
+            }
+            let decode = merge_option(
+                &quote_spanned! { span=> self.#field_ident },
+                &ty,
+                &scalar_ty,
+            );
             decode_field.extend(quote_spanned! { span=>
-                #id => self.#field_ident = Some(Scalar::<#scalar_ty>::decode(decoder)?),
+                #id => #decode,
+            });
+            merge_field.extend(quote_spanned! { span=>
+                #id => <#ty as Scalar::<#scalar_ty>>::merge(&mut message.#member, decoder)?,
             });
 
             encode_impl.extend(quote_spanned! { span=>
-                <#ty as Scalar::<#scalar_ty>>::encode_field(&self.#field_ident, #id, encoder);
+                <#ty as Scalar::<#scalar_ty>>::encode_field(&self.#member, #id, encoder);
             });
         }
     }
 
+    let construct = if is_tuple {
+        quote_spanned! {span=> #ty(#finish_field) }
+    } else {
+        quote_spanned! {span=> #ty { #finish_field } }
+    };
+
+    let (_, ty_generics, struct_where_clause) = generics.split_for_impl();
+    let (impl_generics, _, where_clause) = bounded_generics.split_for_impl();
+    let builder_turbofish = ty_generics.as_turbofish();
+    let ty_name = ty.to_string();
+
     quote_spanned! {span=>
         #[automatically_derived]
         #[allow(unused_imports)]
-        impl #root::Message for #ty {
+        impl #impl_generics #root::Message for #ty #ty_generics #where_clause {
             #[inline]
             fn encode_message(&self, encoder: &mut impl #root::Encode) {
                 use #root::Scalar;
@@ -224,24 +584,39 @@ fn expand_struct_message(
             where
                 Self: Sized,
             {
-                let mut builder = #builder_ident::new();
+                let mut builder = #builder_ident #builder_turbofish::new();
 
                 while !decoder.eof() {
                     let tag = decoder.decode_tag()?;
-                    builder.decode_field(tag, decoder)?;
+                    builder
+                        .decode_field(tag, decoder)
+                        .map_err(|err| #builder_ident #builder_turbofish::field_error(err, tag))?;
                 }
 
                 builder.finish()
             }
+
+            #[inline]
+            fn merge_message(&mut self, decoder: &mut impl #root::Decode) -> Result<(), #root::ProtoError>
+            where
+                Self: Sized,
+            {
+                while !decoder.eof() {
+                    let tag = decoder.decode_tag()?;
+                    #builder_ident #builder_turbofish::merge_field(self, tag, decoder)
+                        .map_err(|err| #builder_ident #builder_turbofish::field_error(err, tag))?;
+                }
+                Ok(())
+            }
         }
 
         #[derive(Default)]
-        struct #builder_ident {
+        struct #builder_ident #generics #struct_where_clause {
             #builder_fields
         }
 
         #[allow(unused_imports)]
-        impl #builder_ident {
+        impl #impl_generics #builder_ident #ty_generics #where_clause {
             fn new() -> Self {
                 Self {
                     #builder_new
@@ -249,13 +624,12 @@ fn expand_struct_message(
             }
 
             #[inline]
-            fn finish(self) -> Result<#ty, #root::ProtoError> {
+            #[allow(clippy::unnecessary_lazy_evaluations, clippy::redundant_closure)]
+            fn finish(self) -> Result<#ty #ty_generics, #root::ProtoError> {
                 let Self {
                     #builder_destructuring
                 } = self;
-                Ok(#ty {
-                    #finish_field
-                })
+                Ok(#construct)
             }
 
             #[inline]
@@ -270,10 +644,63 @@ fn expand_struct_message(
 
                 match tag.field_number() {
                     #decode_field
-                    _ => {}
+                    _ => #unknown_field,
+                }
+                Ok(())
+            }
+
+            #[inline]
+            fn merge_field(
+                message: &mut #ty #ty_generics,
+                tag: #root::Tag,
+                decoder: &mut impl #root::Decode,
+            ) -> Result<(), #root::ProtoError> {
+                use #root::{Scalar, scalars::*};
+
+                #merge_oneof_match
+
+                match tag.field_number() {
+                    #merge_field
+                    _ => #merge_unknown_field,
                 }
                 Ok(())
             }
+
+            fn field_name(field_number: u32) -> Option<&'static str> {
+                match field_number {
+                    #field_names
+                    _ => None,
+                }
+            }
+
+            /// prefixes the error with the name of the field, or its number for unknown fields
+            #[cold]
+            fn field_error(err: #root::ProtoError, tag: #root::Tag) -> #root::ProtoError {
+                match Self::field_name(tag.field_number()) {
+                    Some(name) => err.in_field(#ty_name, name),
+                    None => err.in_field(#ty_name, &tag.field_number().to_string()),
+                }
+            }
+        }
+    }
+}
+
+/// decodes a group into an `Option`, merging into an existing value
+fn merge_group(target: &TokenStream, id: &syn::LitInt) -> TokenStream {
+    quote::quote! {
+        match &mut #target {
+            Some(value) => decoder.merge_group(#id, value)?,
+            None => #target = Some(decoder.decode_group(#id)?),
+        }
+    }
+}
+
+/// decodes into an `Option`, merging into an existing value
+fn merge_option(target: &TokenStream, inner: &syn::Type, scalar_ty: &TokenStream) -> TokenStream {
+    quote::quote! {
+        match &mut #target {
+            Some(value) => <#inner as Scalar::<#scalar_ty>>::merge(value, decoder)?,
+            None => #target = Some(<#inner as Scalar::<#scalar_ty>>::decode(decoder)?),
         }
     }
 }
@@ -285,20 +712,47 @@ pub(crate) fn expand_enumeration(
     let ty = input.ident;
     let span = ty.span();
 
-    let variants = input
-        .data
-        .take_enum()
-        .expect("Enumeration derive only works on unit enumerations");
+    let Some(variants) = input.data.take_enum() else {
+        return compile_error(
+            &ty,
+            "Enumeration can only be derived for enums with unit variants",
+        );
+    };
 
     let mut encode_impl = TokenStream::new();
     let mut decode_impl = TokenStream::new();
+    let mut as_str_name = TokenStream::new();
+    let mut from_str_name = TokenStream::new();
+    let mut all_variants = TokenStream::new();
 
     for variant in variants {
         let var_ident = variant.ident;
         let span = var_ident.span();
-        let id = match variant.id.base10_parse::<i32>() {
+        let name = variant
+            .name
+            .map(|name| name.value())
+            .unwrap_or_else(|| var_ident.to_string());
+
+        as_str_name.extend(quote_spanned! {span=>
+            Self::#var_ident => #name,
+        });
+        from_str_name.extend(quote_spanned! {span=>
+            #name => Some(Self::#var_ident),
+        });
+        all_variants.extend(quote_spanned! {span=>
+            Self::#var_ident,
+        });
+
+        if matches!(input.repr, Repr::String) {
+            continue;
+        }
+
+        let Some(id) = variant.id else {
+            return compile_error(&var_ident, "missing variant number, add `#[gin(id = N)]`");
+        };
+        let id = match id.base10_parse::<i32>() {
             Ok(id) => id,
-            Err(_) => return quote_spanned! {span=> compile_error!("field number (id) is no i32")},
+            Err(_) => return compile_error(&id, "enum variant id must be an i32"),
         };
 
         encode_impl.extend(quote_spanned! {span=>
@@ -310,37 +764,105 @@ pub(crate) fn expand_enumeration(
         });
     }
 
-    quote_spanned! {span=>
-        #[automatically_derived]
-        #[allow(unused_imports)]
-        impl #root::PackableMarker<#root::scalars::Int32> for #ty {}
+    let scalar_impl = match input.repr {
+        Repr::Int32 => quote_spanned! {span=>
+            #[automatically_derived]
+            #[allow(unused_imports)]
+            impl #root::PackableMarker<#root::scalars::Int32> for #ty {}
 
-        #[automatically_derived]
-        #[allow(unused_imports)]
-        impl #root::Scalar<#root::scalars::Int32> for #ty {
-            const WIRE_TYPE: u8 = #root::WIRE_TYPE_VARINT;
+            #[automatically_derived]
+            #[allow(unused_imports)]
+            impl #root::Scalar<#root::scalars::Int32> for #ty {
+                const WIRE_TYPE: u8 = #root::WIRE_TYPE_VARINT;
 
-            #[inline]
-            fn encode(&self, encoder: &mut impl #root::Encode) {
-                let value = match self {
-                    #encode_impl
-                };
+                #[inline]
+                fn encode(&self, encoder: &mut impl #root::Encode) {
+                    let value = match self {
+                        #encode_impl
+                    };
 
-                <i32 as #root::Scalar::<#root::scalars::Int32>>::encode(&value, encoder)
+                    <i32 as #root::Scalar::<#root::scalars::Int32>>::encode(&value, encoder)
+                }
+
+                #[inline]
+                fn decode(decoder: &mut impl #root::Decode) -> Result<Self, #root::ProtoError>
+                where
+                    Self: Sized
+                {
+                    let value = <i32 as #root::Scalar<#root::scalars::Int32>>::decode(decoder)?;
+
+                    match value {
+                        #decode_impl
+                        n => Err(#root::ProtoError::UnknownEnumVariant(n)),
+                    }
+                }
             }
+        },
+        Repr::String => quote_spanned! {span=>
+            #[automatically_derived]
+            #[allow(unused_imports)]
+            impl #root::Scalar<#root::scalars::ProtoString> for #ty {
+                const WIRE_TYPE: u8 = #root::WIRE_TYPE_LENGTH_ENCODED;
+
+                #[inline]
+                fn encode(&self, encoder: &mut impl #root::Encode) {
+                    encoder.encode_str(self.as_str_name())
+                }
 
-            #[inline]
-            fn decode(decoder: &mut impl #root::Decode) -> Result<Self, #root::ProtoError>
-            where
-                Self: Sized
-            {
-                let value = <i32 as #root::Scalar<#root::scalars::Int32>>::decode(decoder)?;
+                #[inline]
+                fn decode(decoder: &mut impl #root::Decode) -> Result<Self, #root::ProtoError>
+                where
+                    Self: Sized
+                {
+                    let value = decoder.decode_string()?;
 
+                    Self::from_str_name(&value).ok_or(#root::ProtoError::UnknownEnumName(value))
+                }
+            }
+        },
+    };
+
+    quote_spanned! {span=>
+        #scalar_impl
+
+        #[automatically_derived]
+        #[allow(unused)]
+        impl #ty {
+            /// the proto value name of the variant
+            pub fn as_str_name(&self) -> &'static str {
+                match self {
+                    #as_str_name
+                }
+            }
+
+            /// the variant for a proto value name
+            pub fn from_str_name(value: &str) -> Option<Self> {
                 match value {
-                    #decode_impl
-                    n => Err(#root::ProtoError::UnknownEnumVariant(n)),
+                    #from_str_name
+                    _ => None,
                 }
             }
+
+            /// every variant in declaration order
+            pub fn all_variants() -> impl Iterator<Item = Self> {
+                [#all_variants].into_iter()
+            }
+        }
+
+        #[automatically_derived]
+        impl ::core::fmt::Display for #ty {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                f.write_str(self.as_str_name())
+            }
+        }
+
+        #[automatically_derived]
+        impl ::core::str::FromStr for #ty {
+            type Err = #root::ProtoError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                Self::from_str_name(s).ok_or_else(|| #root::ProtoError::UnknownEnumName(s.to_owned()))
+            }
         }
     }
 }
@@ -351,26 +873,39 @@ pub(crate) fn one_of_enumeration(
 ) -> TokenStream {
     let ty = input.ident;
 
-    let variants = input
-        .data
-        .take_enum()
-        .expect("OneOf derive only works on newtype enums");
+    let Some(variants) = input.data.take_enum() else {
+        return compile_error(
+            &ty,
+            "OneOf can only be derived for enums with newtype variants",
+        );
+    };
 
     let span = ty.span();
 
     let mut encode_impl = TokenStream::new();
     let mut decode_impl = TokenStream::new();
+    let mut merge_impl = TokenStream::new();
     let mut ids = TokenStream::new();
 
     for variant in variants.into_iter() {
-        let Some(field_ty) = variant.fields.fields.first() else {
-            panic!("OneOfs must contain fields for now");
+        let [field_ty] = variant.fields.fields.as_slice() else {
+            return compile_error(
+                &variant.ident,
+                "OneOf variants must contain exactly one field, e.g. `A(String)`",
+            );
         };
         let var_ident = variant.ident;
         let span = var_ident.span();
         let id = variant.id;
 
-        let scalar_ty = match variant.scalar {
+        let scalar = match variant.utf8 {
+            Some(utf8) => match utf8.apply(variant.scalar, field_ty) {
+                Ok(scalar) => scalar,
+                Err(err) => return err,
+            },
+            None => variant.scalar,
+        };
+        let scalar_ty = match scalar {
             Some(scalar) => scalar.scalar_token(root),
             None => field_ty.scalar_token(root),
         };
@@ -386,6 +921,11 @@ pub(crate) fn one_of_enumeration(
         decode_impl.extend(quote_spanned! {span=>
             #id => return Ok(Self::#var_ident(<#field_ty as Scalar<#scalar_ty>>::decode(decoder)?)),
         });
+
+        // the same variant merges, e.g. a message repeated on the wire, others replace the value
+        merge_impl.extend(quote_spanned! {span=>
+            (#id, Self::#var_ident(value)) => return <#field_ty as Scalar<#scalar_ty>>::merge(value, decoder),
+        });
     }
 
     quote_spanned! {span=>
@@ -406,12 +946,15 @@ pub(crate) fn one_of_enumeration(
             where
                 Self: Sized,
             {
-                let mut slf = None;
+                let mut slf: Option<Self> = None;
 
                 while !decoder.eof() {
                     let tag = decoder.decode_tag()?;
 
-                    slf = Some(Self::decode_field(tag, decoder)?);
+                    match &mut slf {
+                        Some(value) => value.merge_field(tag, decoder)?,
+                        None => slf = Some(Self::decode_field(tag, decoder)?),
+                    }
                 }
 
                 slf.ok_or(#root::ProtoError::MissingOneOf(&[#ids]))
@@ -420,13 +963,15 @@ pub(crate) fn one_of_enumeration(
 
         #[allow(unused_imports)]
         impl #ty {
+            #[doc(hidden)]
             #[allow(unused)]
-            fn matches_tag(tag: #root::Tag,)  -> bool {
+            pub fn matches_tag(tag: #root::Tag,)  -> bool {
                 [#ids].contains(&tag.field_number())
             }
 
+            #[doc(hidden)]
             #[inline]
-            fn decode_field(
+            pub fn decode_field(
                 tag: #root::Tag,
                 decoder: &mut impl #root::Decode,
             ) -> Result<Self, #root::ProtoError> {
@@ -439,6 +984,38 @@ pub(crate) fn one_of_enumeration(
 
                 Err(#root::ProtoError::MissingOneOf(&[#ids]))
             }
+
+            #[doc(hidden)]
+            #[inline]
+            pub fn merge_field(
+                &mut self,
+                tag: #root::Tag,
+                decoder: &mut impl #root::Decode,
+            ) -> Result<(), #root::ProtoError> {
+                use #root::{Scalar, scalars::*};
+
+                #[allow(unreachable_patterns)]
+                match (tag.field_number(), &mut *self) {
+                    #merge_impl
+                    _ => {}
+                }
+
+                *self = Self::decode_field(tag, decoder)?;
+                Ok(())
+            }
         }
     }
 }
+
+/// whether the type refers to any of the type parameters, e.g. `Vec<T>` for `struct Page<T>`
+fn mentions_type_param(ty: &syn::Type, generics: &Generics) -> bool {
+    fn walk(tokens: TokenStream, generics: &Generics) -> bool {
+        tokens.into_iter().any(|token| match token {
+            TokenTree::Ident(ident) => generics.type_params().any(|param| param.ident == ident),
+            TokenTree::Group(group) => walk(group.stream(), generics),
+            _ => false,
+        })
+    }
+
+    walk(ty.to_token_stream(), generics)
+}
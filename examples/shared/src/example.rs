@@ -123,7 +123,11 @@ pub mod example_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let method = echoSvc(inner);
-                        let codec = ::gin_tonic::GinCodec::default();
+                        let codec = ::gin_tonic::GinCodec::<
+                            _,
+                            _,
+                            ::gin_tonic::DefaultStatusMapping,
+                        >::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
                                 accept_compression_encodings,
@@ -270,7 +274,11 @@ pub mod example_client {
                         format!("Service was not ready: {}", e.into()),
                     )
                 })?;
-            let codec = ::gin_tonic::GinCodec::default();
+            let codec = ::gin_tonic::GinCodec::<
+                _,
+                _,
+                ::gin_tonic::DefaultStatusMapping,
+            >::default();
             let path = http::uri::PathAndQuery::from_static("/example.Example/echo");
             let mut req = request.into_request();
             req.extensions_mut().insert(GrpcMethod::new("example.Example", "echo"));
@@ -2,18 +2,66 @@
 
 use std::marker::PhantomData;
 
-use gin_tonic_core::{Message, decoder::Decoder, encoder::Encoder};
-use protox::prost::bytes::{Buf, BufMut};
+use bytes::{Buf, BufMut};
+#[cfg(feature = "bytes")]
+use gin_tonic_core::decoder::BytesDecoder;
+#[cfg(not(feature = "bytes"))]
+use gin_tonic_core::decoder::Decoder;
+use gin_tonic_core::{Message, ProtoError, decoder::DecodeOptions, encoder::Encoder};
 use tonic::codec::{DecodeBuf, EncodeBuf};
 
+/// turns errors of decoding a message into the [tonic::Status] returned to the caller
+pub trait StatusMapping {
+    fn decode_error(err: ProtoError) -> tonic::Status;
+}
+
+/// malformed messages are reported as [tonic::Code::InvalidArgument], exceeded decoding limits as
+/// [tonic::Code::ResourceExhausted] and I/O errors as [tonic::Code::Internal]. the message of the
+/// status contains the path of the failing field
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultStatusMapping;
+
+impl StatusMapping for DefaultStatusMapping {
+    fn decode_error(err: ProtoError) -> tonic::Status {
+        fn code(err: &ProtoError) -> tonic::Code {
+            match err {
+                ProtoError::Field { source, .. } => code(source),
+                ProtoError::Io(_) => tonic::Code::Internal,
+                ProtoError::MessageTooLarge(_)
+                | ProtoError::LengthTooLarge(_)
+                | ProtoError::TooManyElements(_)
+                | ProtoError::RecursionLimitExceeded(_) => tonic::Code::ResourceExhausted,
+                _ => tonic::Code::InvalidArgument,
+            }
+        }
+
+        tonic::Status::new(code(&err), err.to_string())
+    }
+}
+
+/// the [StatusMapping] used for decoding errors can be changed using the type parameter `M`,
+/// see `CompileConfig::status_mapping` for generated services. messages are decoded with the
+/// default [DecodeOptions] unless set by [GinCodec::with_decode_options]
 #[derive(Debug, Clone)]
-pub struct GinCodec<T, U> {
-    _pd: PhantomData<(T, U)>,
+pub struct GinCodec<T, U, M = DefaultStatusMapping> {
+    options: DecodeOptions,
+    _pd: PhantomData<(T, U, M)>,
 }
 
-impl<T, U> Default for GinCodec<T, U> {
+impl<T, U, M> Default for GinCodec<T, U, M> {
     fn default() -> Self {
-        Self { _pd: PhantomData }
+        Self {
+            options: DecodeOptions::new(),
+            _pd: PhantomData,
+        }
+    }
+}
+
+impl<T, U, M> GinCodec<T, U, M> {
+    /// limits of decoding messages with this codec
+    pub fn with_decode_options(mut self, options: DecodeOptions) -> Self {
+        self.options = options;
+        self
     }
 }
 
@@ -23,26 +71,31 @@ pub struct GinEncoder<T> {
 }
 
 #[derive(Debug, Clone)]
-pub struct GinDecoder<U> {
-    _pd: PhantomData<U>,
+pub struct GinDecoder<U, M = DefaultStatusMapping> {
+    options: DecodeOptions,
+    _pd: PhantomData<(U, M)>,
 }
 
-impl<T, U> tonic::codec::Codec for GinCodec<T, U>
+impl<T, U, M> tonic::codec::Codec for GinCodec<T, U, M>
 where
     T: Message + Send + 'static + std::fmt::Debug,
     U: Message + Send + 'static + std::fmt::Debug,
+    M: StatusMapping + Send + 'static,
 {
     type Encode = T;
     type Decode = U;
     type Encoder = GinEncoder<T>;
-    type Decoder = GinDecoder<U>;
+    type Decoder = GinDecoder<U, M>;
 
     fn encoder(&mut self) -> Self::Encoder {
         GinEncoder { _pd: PhantomData }
     }
 
     fn decoder(&mut self) -> Self::Decoder {
-        GinDecoder { _pd: PhantomData }
+        GinDecoder {
+            options: self.options,
+            _pd: PhantomData,
+        }
     }
 }
 
@@ -63,20 +116,30 @@ impl<T: Message + std::fmt::Debug> tonic::codec::Encoder for GinEncoder<T> {
     }
 }
 
-impl<U: Message + std::fmt::Debug> tonic::codec::Decoder for GinDecoder<U> {
+impl<U: Message + std::fmt::Debug, M: StatusMapping> tonic::codec::Decoder for GinDecoder<U, M> {
     type Item = U;
     type Error = tonic::Status;
 
+    #[cfg(not(feature = "bytes"))]
     fn decode(&mut self, src: &mut DecodeBuf<'_>) -> Result<Option<Self::Item>, Self::Error> {
         let len = src.remaining();
-        let decoded =
-            Self::Item::decode_message(&mut Decoder::new(src.chunk())).map_err(map_core_err)?;
+        let decoded = Decoder::with_options(src.chunk(), self.options)
+            .and_then(|mut decoder| Self::Item::decode_message(&mut decoder))
+            .map_err(M::decode_error)?;
         src.advance(len);
 
         Ok(Some(decoded))
     }
-}
 
-fn map_core_err(err: gin_tonic_core::ProtoError) -> tonic::Status {
-    tonic::Status::internal(err.to_string())
+    #[cfg(feature = "bytes")]
+    fn decode(&mut self, src: &mut DecodeBuf<'_>) -> Result<Option<Self::Item>, Self::Error> {
+        // taking the frame as `Bytes` does not copy, so `bytes::Bytes` fields can slice into it
+        let len = src.remaining();
+        let frame = src.copy_to_bytes(len);
+        let decoded = BytesDecoder::with_options(frame, self.options)
+            .and_then(|mut decoder| Self::Item::decode_message(&mut decoder))
+            .map_err(M::decode_error)?;
+
+        Ok(Some(decoded))
+    }
 }
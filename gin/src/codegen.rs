@@ -1,19 +1,24 @@
 pub(crate) mod case;
 pub(crate) mod ctx;
 pub(crate) mod enums;
+pub(crate) mod extensions;
 pub mod external_type;
+pub mod map_type;
 pub(crate) mod messages;
 pub(crate) mod module;
 pub(crate) mod one_of;
 #[cfg(feature = "tonic")]
 pub(crate) mod service;
+pub mod utf8;
 pub(crate) mod utils;
 
 use std::path::PathBuf;
 
 pub use ctx::Generator;
 use external_type::ExternalType;
+pub use map_type::MapType;
 use protox::file::{ChainFileResolver, GoogleFileResolver, IncludeFileResolver};
+pub use utf8::Utf8Validation;
 
 /// [CompileConfig] transforming `*.proto` files into Rust code
 pub struct CompileConfig {
@@ -72,6 +77,69 @@ impl CompileConfig {
         self
     }
 
+    /// Map `bytes` fields matching the pattern to `bytes::Bytes` instead of `Vec<u8>`.
+    /// Requires the `bytes` feature of gin-tonic in the crate using the generated code.
+    ///
+    /// pattern = "." - All bytes fields
+    /// pattern = ".package.v1.MyRequest.payload" - Only the "payload" field of "MyRequest"
+    pub fn bytes(mut self, pattern: impl Into<String>) -> Self {
+        self.ctx.add_bytes(pattern);
+        self
+    }
+
+    /// Use the given container for map fields matching the pattern, patterns are matched against
+    /// the fully qualified field name. The `gin_tonic.v1.map_type` field option takes precedence.
+    ///
+    /// pattern = "." - All map fields
+    /// pattern = ".package.v1.MyRequest.counts" - Only the "counts" field of "MyRequest"
+    pub fn map_type(mut self, map_type: MapType, pattern: impl Into<String>) -> Self {
+        self.ctx.add_map_type(map_type, pattern);
+        self
+    }
+
+    /// Embed the encoded `google.protobuf.FileDescriptorSet` of every package, including the files
+    /// it depends on, as `FILE_DESCRIPTOR_SET` in the generated package module.
+    pub fn file_descriptor_set(mut self) -> Self {
+        self.ctx.file_descriptor_set();
+        self
+    }
+
+    /// Embed the file descriptor sets, see [CompileConfig::file_descriptor_set], and implement
+    /// `gin_tonic::reflection::Descriptor` for generated messages. The crate using the generated
+    /// code needs the `reflection` feature of gin-tonic.
+    pub fn reflection(mut self) -> Self {
+        self.ctx.reflection();
+        self
+    }
+
+    /// Validate UTF-8 of string fields matching the pattern as given, patterns are matched
+    /// against the fully qualified field name. Map fields are always validated strictly.
+    ///
+    /// pattern = "." - All string fields
+    /// pattern = ".package.v1.MyRequest.name" - Only the "name" field of "MyRequest"
+    pub fn utf8(mut self, validation: Utf8Validation, pattern: impl Into<String>) -> Self {
+        self.ctx.add_utf8_validation(validation, pattern);
+        self
+    }
+
+    /// proto3 scalar and enum fields matching the pattern fall back to their zero value when
+    /// absent instead of failing with `ProtoError::MissingField`, as other implementations skip
+    /// encoding zero values. Patterns are matched against the fully qualified field name.
+    ///
+    /// pattern = "." - All proto3 fields
+    /// pattern = ".package.v1.MyRequest.count" - Only the "count" field of "MyRequest"
+    pub fn zero_defaults(mut self, pattern: impl Into<String>) -> Self {
+        self.ctx.add_zero_default(pattern);
+        self
+    }
+
+    /// Path of the gin-tonic crate in generated code, defaults to `::gin_tonic`. Needed when the
+    /// crate is renamed or re-exported, generated derives get `#[gin(root = ...)]`.
+    pub fn root(mut self, path: impl Into<String>) -> Self {
+        self.ctx.root(path);
+        self
+    }
+
     /// import an external type
     pub fn import<I: IntoIterator<Item = ExternalType>>(&mut self, paths: I) {
         self.ctx.import(paths);
@@ -97,6 +165,15 @@ impl CompileConfig {
         self
     }
 
+    /// Use the given [StatusMapping](crate::StatusMapping) for decoding errors of generated
+    /// services, e.g. `crate::MyStatusMapping`. Defaults to
+    /// [DefaultStatusMapping](crate::DefaultStatusMapping) of the [root](CompileConfig::root).
+    #[cfg(feature = "tonic")]
+    pub fn status_mapping(mut self, path: impl Into<String>) -> Self {
+        self.ctx.status_mapping(path);
+        self
+    }
+
     pub fn include(mut self, path: impl Into<PathBuf>) -> Self {
         let path = path.into();
         tracing::debug!("adding include '{}'", path.display());
@@ -218,16 +295,41 @@ pub enum CompilerError {
 }
 
 /// protox [protox::file::Resolver]
-struct Resolver(ChainFileResolver);
+struct Resolver {
+    resolver: ChainFileResolver,
+    include_directories: Vec<PathBuf>,
+}
 
 impl Resolver {
     pub fn new(include_directories: impl IntoIterator<Item = PathBuf>) -> Self {
+        let include_directories = include_directories.into_iter().collect::<Vec<_>>();
         let mut resolver = ChainFileResolver::new();
-        for include in include_directories.into_iter() {
-            resolver.add(IncludeFileResolver::new(include));
+        for include in include_directories.iter() {
+            resolver.add(IncludeFileResolver::new(include.clone()));
         }
         resolver.add(GoogleFileResolver::new());
-        Self(resolver)
+        Self {
+            resolver,
+            include_directories,
+        }
+    }
+
+    /// protox fails to parse edition files with a generic syntax error, check whether the file
+    /// declares an edition to give a clear error instead
+    fn declares_edition(&self, name: &str) -> bool {
+        let Some(source) = self
+            .include_directories
+            .iter()
+            .find_map(|include| std::fs::read_to_string(include.join(name)).ok())
+        else {
+            return false;
+        };
+
+        source
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty() && !line.starts_with("//"))
+            .is_some_and(|line| line.starts_with("edition"))
     }
 }
 
@@ -235,14 +337,22 @@ const GIN_PROTO: &str = include_str!("../proto/gin.proto");
 
 impl protox::file::FileResolver for Resolver {
     fn resolve_path(&self, path: &std::path::Path) -> Option<String> {
-        self.0.resolve_path(path)
+        self.resolver.resolve_path(path)
     }
 
     fn open_file(&self, name: &str) -> Result<protox::file::File, protox::Error> {
         if name.ends_with("gin.proto") {
             return protox::file::File::from_source(name, GIN_PROTO);
         }
-        let file = self.0.open_file(name)?;
+        let file = self.resolver.open_file(name).map_err(|err| {
+            if err.is_parse() && self.declares_edition(name) {
+                protox::Error::new(format!(
+                    "{name}: protobuf editions are not supported, declare `syntax = \"proto2\"` or `syntax = \"proto3\"` instead"
+                ))
+            } else {
+                err
+            }
+        })?;
 
         if let Some(path) = file.path() {
             println!("cargo:rerun-if-changed={}", path.display());
@@ -10,12 +10,15 @@ pub enum Case {
     Pascal,
     /// snake_case
     Snake,
+    /// SCREAMING_SNAKE_CASE
+    ScreamingSnake,
 }
 
 pub fn convert(input: &str, case: Case) -> Cow<'_, str> {
     let converted: String = match case {
         Case::UpperCamel | Case::Pascal => heck::AsPascalCase(input).to_string(),
         Case::Snake => heck::AsSnakeCase(input).to_string(),
+        Case::ScreamingSnake => heck::AsShoutySnakeCase(input).to_string(),
     };
 
     if converted == input {
@@ -4,17 +4,28 @@ use protox::prost_reflect::DescriptorPool;
 use crate::CompilerError;
 use crate::codegen::case::{Case, convert};
 use crate::codegen::external_type::ExternalType;
-use crate::codegen::module::Module;
-use crate::codegen::{enums, external_type, messages, service, utils};
+use crate::codegen::map_type::MapType;
+use crate::codegen::module::{self, Module};
+use crate::codegen::utf8::Utf8Validation;
+use crate::codegen::{enums, extensions, external_type, messages, service, utils};
 
 pub struct Generator {
     well_known_types: bool,
     external_types: Vec<ExternalType>,
     type_filter: Box<dyn for<'a> Fn(&'a str) -> bool>,
     type_attributes: Vec<(String, String)>,
+    bytes_patterns: Vec<String>,
+    map_types: Vec<(String, MapType)>,
+    utf8_validations: Vec<(String, Utf8Validation)>,
+    zero_default_patterns: Vec<String>,
+    file_descriptor_set: bool,
+    reflection: bool,
+    root: Option<String>,
 
     #[cfg(feature = "tonic")]
     generate_services: bool,
+    #[cfg(feature = "tonic")]
+    status_mapping: Option<String>,
 }
 
 impl Default for Generator {
@@ -37,9 +48,18 @@ impl Generator {
             external_types: vec![],
             type_filter: Box::new(filter),
             type_attributes: vec![],
+            bytes_patterns: vec![],
+            map_types: vec![],
+            utf8_validations: vec![],
+            zero_default_patterns: vec![],
+            file_descriptor_set: false,
+            reflection: false,
+            root: None,
 
             #[cfg(feature = "tonic")]
             generate_services: true,
+            #[cfg(feature = "tonic")]
+            status_mapping: None,
         }
     }
 
@@ -49,6 +69,19 @@ impl Generator {
         self.generate_services = false;
     }
 
+    /// Path of the [StatusMapping](crate::StatusMapping) used by the codec of generated services,
+    /// `DefaultStatusMapping` of the [root](Generator::root) by default.
+    #[cfg(feature = "tonic")]
+    pub fn status_mapping(&mut self, path: impl Into<String>) {
+        self.status_mapping = Some(path.into());
+    }
+
+    /// Path of the gin-tonic crate in generated code, `::gin_tonic` by default. Derives get a
+    /// `#[gin(root = ...)]` attribute when it is set, e.g. to `crate` within gin-tonic itself.
+    pub fn root(&mut self, path: impl Into<String>) {
+        self.root = Some(path.into());
+    }
+
     /// If the pattern starts with a dot, then the pattern is a prefix match
     /// pattern = "." - Matches everything
     /// pattern = ".package.v1.MyRequest" - Matches all "MyRequest" types
@@ -73,6 +106,82 @@ impl Generator {
         }
     }
 
+    /// map `bytes` fields matching the pattern to `bytes::Bytes` instead of `Vec<u8>`. patterns are
+    /// matched against the fully qualified field name the same way as in [Generator::add_attribute]
+    pub fn add_bytes(&mut self, pattern: impl Into<String>) {
+        let pattern = pattern.into();
+        if !pattern.is_empty() {
+            tracing::debug!("adding bytes pattern '{pattern}'");
+            self.bytes_patterns.push(pattern);
+        }
+    }
+
+    /// use the given container for map fields matching the pattern. patterns are matched against
+    /// the fully qualified field name the same way as in [Generator::add_attribute]
+    pub fn add_map_type(&mut self, map_type: MapType, pattern: impl Into<String>) {
+        let pattern = pattern.into();
+        if !pattern.is_empty() {
+            tracing::debug!("adding map type {map_type:?} with pattern '{pattern}'");
+            self.map_types.push((pattern, map_type));
+        }
+    }
+
+    /// Validate UTF-8 of string fields matching the pattern as given.
+    pub fn add_utf8_validation(&mut self, validation: Utf8Validation, pattern: impl Into<String>) {
+        let pattern = pattern.into();
+        if !pattern.is_empty() {
+            tracing::debug!("adding utf8 validation {validation:?} with pattern '{pattern}'");
+            self.utf8_validations.push((pattern, validation));
+        }
+    }
+
+    /// proto3 scalar and enum fields matching the pattern fall back to their zero value when
+    /// absent, as other implementations skip encoding zero values. Without a match they are
+    /// required. patterns are matched against the fully qualified field name the same way as in
+    /// [Generator::add_attribute]
+    pub fn add_zero_default(&mut self, pattern: impl Into<String>) {
+        let pattern = pattern.into();
+        if !pattern.is_empty() {
+            tracing::debug!("adding zero default pattern '{pattern}'");
+            self.zero_default_patterns.push(pattern);
+        }
+    }
+
+    /// Embed the encoded `FileDescriptorSet` as `FILE_DESCRIPTOR_SET` in every package module.
+    pub fn file_descriptor_set(&mut self) {
+        self.file_descriptor_set = true;
+    }
+
+    /// Embed the file descriptor set and implement `gin_tonic::reflection::Descriptor` for
+    /// generated messages.
+    pub fn reflection(&mut self) {
+        self.file_descriptor_set = true;
+        self.reflection = true;
+    }
+
+    pub(crate) fn use_reflection(&self) -> bool {
+        self.reflection
+    }
+
+    /// path of the gin-tonic crate in generated code
+    pub(crate) fn root_str(&self) -> &str {
+        self.root.as_deref().unwrap_or("::gin_tonic")
+    }
+
+    pub(crate) fn root_path(&self) -> TokenStream {
+        // TODO Better error message.
+        let path = syn::parse_str::<syn::Path>(self.root_str()).expect("Invalid root path");
+        quote::quote!(#path)
+    }
+
+    /// `#[gin(root = ...)]` for derives if the root is not `::gin_tonic`
+    pub(crate) fn root_attribute(&self) -> TokenStream {
+        match &self.root {
+            Some(root) => quote::quote!(#[gin(root = #root)]),
+            None => quote::quote!(),
+        }
+    }
+
     /// import an external type
     pub fn import<I: IntoIterator<Item = ExternalType>>(&mut self, paths: I) {
         for path in paths.into_iter() {
@@ -102,7 +211,7 @@ impl Generator {
     ) -> Result<(), CompilerError> {
         if self.well_known_types {
             self.external_types
-                .extend(external_type::well_known_types());
+                .extend(external_type::well_known_types(self.root_str()));
         }
 
         let mut root = Module::new("<root>");
@@ -127,11 +236,37 @@ impl Generator {
             messages::generate(&self, &mut root, &module_path, ty);
         }
 
+        for ext in pool.all_extensions() {
+            // only top level extensions here
+            if ext.parent_message().is_some() {
+                continue;
+            }
+
+            let module_path = String::from(ext.package_name());
+            extensions::generate(&self, &mut root, &module_path, ext);
+        }
+
         #[cfg(feature = "tonic")]
         if self.generate_services {
             for svc in pool.services() {
                 let module_path = String::from(svc.package_name());
-                service::generate(&mut root, &module_path, svc);
+                service::generate(&self, &mut root, &module_path, svc);
+            }
+        }
+
+        if self.file_descriptor_set {
+            // the empty package of package-less files resolves to their module as well, the
+            // `Descriptor` impls generated there need its `DESCRIPTOR_POOL`
+            for package in pool.files().map(|file| String::from(file.package_name())) {
+                let Some(module) = module::find_child(&mut root, &package) else {
+                    // nothing was generated for the package
+                    continue;
+                };
+                if module.has_descriptor_set {
+                    continue;
+                }
+                module.has_descriptor_set = true;
+                module.extend(descriptor_set(pool, &package, &self));
             }
         }
 
@@ -163,6 +298,43 @@ impl Generator {
         quote::quote!()
     }
 
+    pub(crate) fn use_zero_default(&self, name: &str) -> bool {
+        self.zero_default_patterns
+            .iter()
+            .any(|pattern| utils::match_name(pattern, name))
+    }
+
+    /// path of the status mapping used by generated services
+    #[cfg(feature = "tonic")]
+    pub(crate) fn status_mapping_path(&self) -> String {
+        match &self.status_mapping {
+            Some(path) => path.clone(),
+            None => format!("{}::DefaultStatusMapping", self.root_str()),
+        }
+    }
+
+    pub(crate) fn use_bytes(&self, name: &str) -> bool {
+        self.bytes_patterns
+            .iter()
+            .any(|pattern| utils::match_name(pattern, name))
+    }
+
+    pub(crate) fn map_type(&self, name: &str) -> MapType {
+        self.map_types
+            .iter()
+            .find(|(pattern, _)| utils::match_name(pattern, name))
+            .map(|(_, map_type)| *map_type)
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn utf8_validation(&self, name: &str) -> Utf8Validation {
+        self.utf8_validations
+            .iter()
+            .find(|(pattern, _)| utils::match_name(pattern, name))
+            .map(|(_, validation)| *validation)
+            .unwrap_or_default()
+    }
+
     pub(crate) fn resolve_ident(&self, identifier: &str) -> Option<String> {
         let identifier = format!(".{}", identifier);
 
@@ -216,3 +388,46 @@ impl Generator {
         None
     }
 }
+
+/// the files of the package and their dependencies, encoded as `google.protobuf.FileDescriptorSet`
+fn descriptor_set(pool: &DescriptorPool, package: &str, ctx: &Generator) -> TokenStream {
+    let mut files = pool
+        .files()
+        .filter(|file| file.package_name() == package)
+        .collect::<Vec<_>>();
+    let mut index = 0;
+    while let Some(file) = files.get(index).cloned() {
+        for dependency in file.dependencies() {
+            if !files.contains(&dependency) {
+                files.push(dependency);
+            }
+        }
+        index += 1;
+    }
+    // the pool lists dependencies before the files using them
+    let files = pool
+        .files()
+        .filter(|file| files.contains(file))
+        .map(|file| file.file_descriptor_proto().clone())
+        .collect();
+
+    let set = protox::prost_reflect::prost_types::FileDescriptorSet { file: files };
+    let bytes = proc_macro2::Literal::byte_string(&protox::prost::Message::encode_to_vec(&set));
+
+    let pool = if ctx.use_reflection() {
+        let root = ctx.root_path();
+        quote::quote! {
+            /// pool decoded from [FILE_DESCRIPTOR_SET]
+            pub static DESCRIPTOR_POOL: #root::reflection::LazyDescriptorPool =
+                #root::reflection::LazyDescriptorPool::new(FILE_DESCRIPTOR_SET);
+        }
+    } else {
+        quote::quote!()
+    };
+
+    quote::quote! {
+        /// encoded `google.protobuf.FileDescriptorSet` of the package including its dependencies
+        pub const FILE_DESCRIPTOR_SET: &[u8] = #bytes;
+        #pool
+    }
+}
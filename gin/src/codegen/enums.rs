@@ -1,4 +1,4 @@
-use protox::prost_reflect::EnumDescriptor;
+use protox::prost_reflect::{EnumDescriptor, EnumValueDescriptor};
 
 use crate::codegen::{Generator, case, module};
 
@@ -23,7 +23,7 @@ pub(crate) fn generate(
 
     let attributes = ctx.attributes(qualified_name);
 
-    let module = module::create_child(parent, module_path);
+    let module = module::create_child(ctx, parent, module_path);
 
     let ty_name = case::convert(ty.name(), case::Case::Pascal);
     let name = quote::format_ident!("{}", ty_name);
@@ -32,29 +32,24 @@ pub(crate) fn generate(
 
     for value in ty.values() {
         let id = value.number();
-        let (_package, value) = value
-            .full_name()
-            .rsplit_once('.')
-            .expect("Enum values should be in a package");
-        if value.ends_with("UNSPECIFIED") {
+        let proto_name = value.name();
+        let Some(value) = variant_name(&ty_name, &value) else {
             // TODO: add support for UNSPECIFIED variant
             continue;
-        }
-        let value = case::convert(value, case::Case::Pascal);
-        let value = value
-            .strip_prefix(ty_name.as_ref())
-            .unwrap_or(value.as_ref());
+        };
 
         let value_name = quote::format_ident!("{}", value);
 
         body.extend(quote::quote! {
-            #[gin(id = #id)]
+            #[gin(id = #id, name = #proto_name)]
             #value_name,
         });
     }
 
+    let root_attribute = ctx.root_attribute();
     let item: syn::ItemEnum = syn::parse_quote! {
         #[derive(Clone, Copy, Debug, Eq, PartialEq, Enumeration)]
+        #root_attribute
         #attributes
         pub enum #name {
             #body
@@ -65,3 +60,19 @@ pub(crate) fn generate(
         #item
     });
 }
+
+/// the Rust variant name of an enumeration value, `None` for `UNSPECIFIED` values which are not
+/// generated
+pub(crate) fn variant_name(ty_name: &str, value: &EnumValueDescriptor) -> Option<String> {
+    let value = value.name();
+    if value.ends_with("UNSPECIFIED") {
+        return None;
+    }
+    let value = case::convert(value, case::Case::Pascal);
+    Some(
+        value
+            .strip_prefix(ty_name)
+            .unwrap_or(value.as_ref())
+            .to_owned(),
+    )
+}
@@ -0,0 +1,67 @@
+use protox::prost_reflect::{ExtensionDescriptor, Kind};
+
+use crate::codegen::{Generator, case, module, utils};
+
+/// generate a typed descriptor for an `extend` declaration
+pub(crate) fn generate(
+    ctx: &Generator,
+    parent: &mut module::Module,
+    module_path: &str,
+    ext: ExtensionDescriptor,
+) {
+    let qualified_name = ext.full_name();
+
+    if !ctx.filter(qualified_name) {
+        return;
+    }
+
+    tracing::info!("generating extension: {qualified_name}");
+
+    let module = module::create_child(ctx, parent, module_path);
+
+    let (scalar, rust_type) = match ext.kind() {
+        Kind::Double => (quote::quote!(Double), quote::quote!(f64)),
+        Kind::Float => (quote::quote!(Float), quote::quote!(f32)),
+        Kind::Int32 => (quote::quote!(Int32), quote::quote!(i32)),
+        Kind::Int64 => (quote::quote!(Int64), quote::quote!(i64)),
+        Kind::Uint32 => (quote::quote!(UInt32), quote::quote!(u32)),
+        Kind::Uint64 => (quote::quote!(UInt64), quote::quote!(u64)),
+        Kind::Sint32 => (quote::quote!(SInt32), quote::quote!(i32)),
+        Kind::Sint64 => (quote::quote!(SInt64), quote::quote!(i64)),
+        Kind::Fixed32 => (quote::quote!(Fixed32), quote::quote!(u32)),
+        Kind::Fixed64 => (quote::quote!(Fixed64), quote::quote!(u64)),
+        Kind::Sfixed32 => (quote::quote!(SFixed32), quote::quote!(i32)),
+        Kind::Sfixed64 => (quote::quote!(SFixed64), quote::quote!(i64)),
+        Kind::Bool => (quote::quote!(Bool), quote::quote!(bool)),
+        Kind::String => (quote::quote!(ProtoString), quote::quote!(String)),
+        Kind::Bytes if ctx.use_bytes(qualified_name) => (quote::quote!(Bytes), {
+            let root = ctx.root_path();
+            quote::quote!(#root::bytes::Bytes)
+        }),
+        Kind::Bytes => (quote::quote!(Bytes), quote::quote!(Vec<u8>)),
+        Kind::Message(ty) => (
+            quote::quote!(Bytes),
+            utils::resolve_message(ctx, qualified_name, ty.full_name()),
+        ),
+        Kind::Enum(ty) => (
+            quote::quote!(Int32),
+            utils::resolve_message(ctx, qualified_name, ty.full_name()),
+        ),
+    };
+
+    let name = case::convert(ext.name(), case::Case::ScreamingSnake);
+    let name = quote::format_ident!("{}", name);
+    let number = ext.number();
+    let doc = format!(
+        " extension `{}` of `{}`",
+        ext.name(),
+        ext.containing_message().full_name()
+    );
+
+    let root = ctx.root_path();
+    module.extend(quote::quote! {
+        #[doc = #doc]
+        pub const #name: #root::Extension<#root::scalars::#scalar, #rust_type> =
+            #root::Extension::new(#number);
+    });
+}
@@ -15,11 +15,11 @@ impl ExternalType {
 }
 
 // some of the well known google protobuf types
-pub fn well_known_types() -> Vec<ExternalType> {
+pub fn well_known_types(root: &str) -> Vec<ExternalType> {
     vec![
         ExternalType::raw(
             ".google.protobuf.Duration",
-            "::gin_tonic::types::google::Duration",
+            format!("{root}::types::google::Duration"),
         ),
         ExternalType::raw(".google.protobuf.BoolValue", "bool"),
         ExternalType::raw(
@@ -33,7 +33,7 @@ pub fn well_known_types() -> Vec<ExternalType> {
         ExternalType::raw(".google.protobuf.Int64Value", "i64"),
         ExternalType::raw(
             ".google.protobuf.StringValue",
-            "::gin_tonic::alloc::string::String",
+            format!("{root}::alloc::string::String"),
         ),
         ExternalType::raw(".google.protobuf.UInt32Value", "u32"),
         ExternalType::raw(".google.protobuf.UInt64Value", "u64"),
@@ -0,0 +1,48 @@
+//! container types for protobuf map fields
+
+use std::str::FromStr;
+
+use proc_macro2::TokenStream;
+
+/// the Rust container generated for a protobuf map field
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MapType {
+    /// `fxhash::FxHashMap`
+    #[default]
+    FxHashMap,
+    /// `std::collections::HashMap` using the std hasher
+    HashMap,
+    /// `std::collections::BTreeMap`, encodes entries in key order
+    BTreeMap,
+    /// `indexmap::IndexMap`, requires the `indexmap` feature
+    IndexMap,
+}
+
+impl MapType {
+    /// `root` is the path of the gin-tonic crate
+    pub(crate) fn path(&self, root: &TokenStream) -> TokenStream {
+        match self {
+            MapType::FxHashMap => quote::quote!(#root::fxhash::FxHashMap),
+            MapType::HashMap => quote::quote!(::std::collections::HashMap),
+            MapType::BTreeMap => quote::quote!(::std::collections::BTreeMap),
+            MapType::IndexMap => quote::quote!(#root::indexmap::IndexMap),
+        }
+    }
+}
+
+/// parses the value of the `gin_tonic.v1.map_type` field option
+impl FromStr for MapType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "fx_hash_map" => Ok(MapType::FxHashMap),
+            "hash_map" => Ok(MapType::HashMap),
+            "btree_map" => Ok(MapType::BTreeMap),
+            "index_map" => Ok(MapType::IndexMap),
+            _ => Err(format!(
+                "unknown map type '{s}', expected one of: fx_hash_map, hash_map, btree_map, index_map"
+            )),
+        }
+    }
+}
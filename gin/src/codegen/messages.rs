@@ -1,6 +1,6 @@
 use protox::prost_reflect::MessageDescriptor;
 
-use crate::codegen::{Generator, case, enums, module, one_of, utils};
+use crate::codegen::{Generator, case, enums, extensions, module, one_of, utils};
 
 /// generate message
 pub(crate) fn generate(
@@ -32,7 +32,7 @@ pub(crate) fn generate(
 
     let attributes = ctx.attributes(qualified_name);
 
-    let module = module::create_child(parent, module_path);
+    let module = module::create_child(ctx, parent, module_path);
 
     let ty_name = case::convert(ty.name(), case::Case::Pascal);
     let name = quote::format_ident!("{}", ty_name);
@@ -57,7 +57,7 @@ pub(crate) fn generate(
             let field_type = utils::resolve_message(ctx, qualified_name, one_of.full_name());
 
             body.extend(quote::quote! {
-                #[gin(id = 0)]
+                #[gin(id = 0, oneof)]
                 pub #field_name: #field_type,
             });
 
@@ -66,7 +66,12 @@ pub(crate) fn generate(
 
         let field_name = quote::format_ident!("{}", field.name());
         let field_type = utils::field_type(ctx, qualified_name, &field);
-        let proto_attributes = utils::proto_attribute(&field);
+        let mut proto_attributes = utils::proto_attribute(&field);
+        proto_attributes.extend(utils::utf8_attribute(ctx, &field));
+        if let Some(default) = utils::default_value(ctx, qualified_name, &field) {
+            let default = default.to_string();
+            proto_attributes.extend(quote::quote! { , default = #default });
+        }
 
         body.extend(quote::quote! {
             #[gin(id = #id #proto_attributes)]
@@ -74,14 +79,44 @@ pub(crate) fn generate(
         });
     }
 
+    let root = ctx.root_path();
+    if ty.extension_ranges().len() > 0 {
+        body.extend(quote::quote! {
+            #[gin(extensions)]
+            pub extensions: #root::ExtensionSet,
+        });
+    }
+
+    let root_attribute = ctx.root_attribute();
     module.extend(quote::quote! {
         #[derive(Clone, Debug, Message)]
+        #root_attribute
         #attributes
         pub struct #name {
             #body
         }
     });
 
+    if ctx.use_reflection() {
+        // the pool is declared in the package module, nested messages live in child modules
+        let mut pool = quote::quote!(DESCRIPTOR_POOL);
+        let mut parent = ty.parent_message();
+        while let Some(message) = parent {
+            pool = quote::quote!(super::#pool);
+            parent = message.parent_message();
+        }
+
+        module.extend(quote::quote! {
+            impl #root::reflection::Descriptor for #name {
+                const FULL_NAME: &'static str = #qualified_name;
+
+                fn descriptor() -> #root::reflection::MessageDescriptor {
+                    #pool.message(Self::FULL_NAME)
+                }
+            }
+        });
+    }
+
     {
         let module_path = ty.name();
 
@@ -94,5 +129,8 @@ pub(crate) fn generate(
         for one_of in ty.oneofs() {
             one_of::generate(ctx, module, module_path, one_of);
         }
+        for ext in ty.child_extensions() {
+            extensions::generate(ctx, module, module_path, ext);
+        }
     }
 }
@@ -3,7 +3,7 @@ use std::path::{Path, PathBuf};
 use proc_macro2::TokenStream;
 use quote::ToTokens;
 
-use crate::codegen::{CompilerError, case, module};
+use crate::codegen::{CompilerError, Generator, case, module};
 
 pub struct Module {
     pub name: String,
@@ -11,6 +11,7 @@ pub struct Module {
     pub path: Vec<String>,
     pub content: TokenStream,
     pub children: Vec<Module>,
+    pub has_descriptor_set: bool,
 }
 
 impl Module {
@@ -29,6 +30,7 @@ impl Module {
             path,
             content: quote::quote!(),
             children: vec![],
+            has_descriptor_set: false,
         }
     }
 
@@ -167,19 +169,39 @@ fn write(tokens: TokenStream, out: impl AsRef<Path>) -> Result<(), CompilerError
 }
 
 pub(crate) fn create_child<'a>(
+    ctx: &Generator,
     parent: &'a mut module::Module,
     module_path: &str,
 ) -> &'a mut module::Module {
-    let module_path = module_path
-        .split('.')
-        .map(|segment| case::convert(segment, case::Case::Snake));
-    let module = parent.create_child_from_path(module_path);
+    let module = parent.create_child_from_path(segments(module_path));
     if module.is_empty() {
+        let root = ctx.root_path();
         let prelude = quote::quote! {
             #[allow(unused_imports)]
-            use ::gin_tonic::{Enumeration, Message, OneOf};
+            use #root::{Enumeration, Message, OneOf};
         };
         module.extend(prelude);
     }
     module
 }
+
+/// the module of a package if anything was generated for it
+pub(crate) fn find_child<'a>(
+    parent: &'a mut module::Module,
+    module_path: &str,
+) -> Option<&'a mut module::Module> {
+    segments(module_path).try_fold(parent, |module, segment| {
+        module
+            .children
+            .iter_mut()
+            .find(|child| child.name == segment)
+    })
+}
+
+/// module names of a package. types of package-less files are generated into a module with an
+/// empty name, which is written to `.rs` next to the package modules
+fn segments(module_path: &str) -> impl Iterator<Item = std::borrow::Cow<'_, str>> {
+    module_path
+        .split('.')
+        .map(|segment| case::convert(segment, case::Case::Snake))
+}
@@ -1,4 +1,4 @@
-use protox::prost_reflect::{FieldDescriptor, MessageDescriptor, OneofDescriptor};
+use protox::prost_reflect::{FieldDescriptor, Kind, MessageDescriptor, OneofDescriptor};
 
 use crate::codegen::{Generator, case, enums, messages, module, utils};
 
@@ -26,12 +26,19 @@ pub(crate) fn generate(
 
     let attributes = ctx.attributes(qualified_name);
 
-    let module = module::create_child(parent, module_path);
+    let module = module::create_child(ctx, parent, module_path);
 
     let ty_name = case::convert(ty.name(), case::Case::Pascal);
     let ty_name = quote::format_ident!("{}", ty_name);
 
     let mut body = quote::quote!();
+    // generated messages do not implement `Eq`, neither do floats
+    let comparable = ty.fields().all(|variant| {
+        !matches!(
+            variant.kind(),
+            Kind::Message(_) | Kind::Float | Kind::Double
+        )
+    });
 
     for variant in ty.fields() {
         let id = variant.number();
@@ -40,7 +47,8 @@ pub(crate) fn generate(
         let variant_name = quote::format_ident!("{}", variant_name);
 
         let variant_type = utils::field_type(ctx, qualified_name, &variant);
-        let proto_attributes = utils::proto_attribute(&variant);
+        let mut proto_attributes = utils::proto_attribute(&variant);
+        proto_attributes.extend(utils::utf8_attribute(ctx, &variant));
 
         if utils::is_unit_type(&variant_type) {
             body.extend(quote::quote! {
@@ -54,8 +62,16 @@ pub(crate) fn generate(
         };
     }
 
+    let derive = if comparable {
+        quote::quote! { #[derive(Clone, Debug, Eq, PartialEq, OneOf)] }
+    } else {
+        quote::quote! { #[derive(Clone, Debug, OneOf)] }
+    };
+
+    let root_attribute = ctx.root_attribute();
     module.extend(quote::quote! {
-        #[derive(Clone, Debug, Eq, PartialEq, OneOf)]
+        #derive
+        #root_attribute
         #attributes
         pub enum #ty_name {
             #body
@@ -76,7 +92,7 @@ pub(crate) fn generate_unwrapped(
 
     let attributes = ctx.attributes(qualified_name);
 
-    let module = module::create_child(parent, module_path);
+    let module = module::create_child(ctx, parent, module_path);
 
     let ty_name = case::convert(parent_message.name(), case::Case::Pascal);
     let ty_name = quote::format_ident!("{}", ty_name);
@@ -89,7 +105,8 @@ pub(crate) fn generate_unwrapped(
         let variant_name = quote::format_ident!("{}", variant_name);
 
         let variant_type = utils::field_type(ctx, qualified_name, &variant);
-        let proto_attributes = utils::proto_attribute(&variant);
+        let mut proto_attributes = utils::proto_attribute(&variant);
+        proto_attributes.extend(utils::utf8_attribute(ctx, &variant));
 
         if utils::is_unit_type(&variant_type) {
             body.extend(quote::quote! {
@@ -104,8 +121,11 @@ pub(crate) fn generate_unwrapped(
         }
     }
 
+    let root = ctx.root_path();
+    let root_attribute = ctx.root_attribute();
     let item: syn::ItemEnum = syn::parse_quote! {
-        #[derive(Clone, Debug, ::gin_tonic::OneOf)]
+        #[derive(Clone, Debug, #root::OneOf)]
+        #root_attribute
         #attributes
         pub enum #ty_name {
             #body
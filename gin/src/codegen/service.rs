@@ -1,25 +1,44 @@
 use protox::prost_reflect::ServiceDescriptor;
 use tonic_build::CodeGenBuilder;
 
-use crate::codegen::{case, module};
+use crate::codegen::{Generator, case, module};
+
+pub(crate) fn generate(
+    ctx: &Generator,
+    parent: &mut module::Module,
+    module_path: &str,
+    svc: ServiceDescriptor,
+) {
+    let codec_path = format!(
+        "{}::GinCodec::<_, _, {}>",
+        ctx.root_str(),
+        ctx.status_mapping_path()
+    );
 
-pub(crate) fn generate(parent: &mut module::Module, module_path: &str, svc: ServiceDescriptor) {
     let mut service = tonic_build::manual::Service::builder()
         .name(svc.name())
         .package(svc.package_name());
 
-    let module = module::create_child(parent, module_path);
+    let module = module::create_child(ctx, parent, module_path);
 
     for method in svc.methods() {
         let route_name = case::convert(method.name(), case::Case::Snake);
 
-        let method = tonic_build::manual::Method::builder()
+        let mut builder = tonic_build::manual::Method::builder()
             .name(route_name)
             .route_name(method.name())
             .input_type(format!("super::{}", method.input().name()))
             .output_type(format!("super::{}", method.output().name()))
-            .codec_path("::gin_tonic::GinCodec")
-            .build();
+            .codec_path(&codec_path);
+
+        if method.is_client_streaming() {
+            builder = builder.client_streaming();
+        }
+        if method.is_server_streaming() {
+            builder = builder.server_streaming();
+        }
+
+        let method = builder.build();
 
         service = service.method(method);
     }
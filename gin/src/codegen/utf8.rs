@@ -0,0 +1,44 @@
+//! validation of string fields
+
+use std::str::FromStr;
+
+use proc_macro2::TokenStream;
+
+/// how generated code validates UTF-8 of `string` fields on decode
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Utf8Validation {
+    /// decoding fails on invalid UTF-8
+    #[default]
+    Strict,
+    /// invalid sequences are replaced with `U+FFFD`
+    Lossy,
+    /// no validation, the field is generated as `Vec<u8>`, or `bytes::Bytes` if it matches a
+    /// bytes pattern
+    None,
+}
+
+impl Utf8Validation {
+    /// the `utf8` argument of the derive attribute, nothing for the default
+    pub(crate) fn attribute(&self) -> TokenStream {
+        match self {
+            Utf8Validation::Strict => quote::quote!(),
+            Utf8Validation::Lossy => quote::quote!(, utf8 = "lossy"),
+            Utf8Validation::None => quote::quote!(, utf8 = "none"),
+        }
+    }
+}
+
+impl FromStr for Utf8Validation {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "strict" => Ok(Utf8Validation::Strict),
+            "lossy" => Ok(Utf8Validation::Lossy),
+            "none" => Ok(Utf8Validation::None),
+            _ => Err(format!(
+                "unknown utf8 validation '{s}', expected one of: strict, lossy, none"
+            )),
+        }
+    }
+}
@@ -2,13 +2,15 @@ use std::borrow::Cow;
 
 use proc_macro2::{Delimiter, TokenStream};
 use protox::prost_reflect::{
-    Cardinality, DescriptorPool, DynamicMessage, FieldDescriptor, Kind, Value,
+    Cardinality, DescriptorPool, DynamicMessage, FieldDescriptor, Kind, Syntax, Value,
 };
 use quote::quote;
 
-use crate::codegen::{Generator, case};
+use crate::codegen::{Generator, MapType, Utf8Validation, case, enums};
+use crate::descriptor;
 
 const RUST_TYPE: &str = ".gin_tonic.v1.rust_type";
+const MAP_TYPE: &str = ".gin_tonic.v1.map_type";
 
 pub fn ext_ref<'a>(
     pool: &DescriptorPool,
@@ -152,7 +154,13 @@ fn relative_path(origin_type: &str, qualified_name: &str) -> TokenStream {
         };
     };
 
-    let segments = diff.split('.').skip(1).map(handler).collect::<Vec<_>>();
+    // the diff starts with a '.' unless one of the packages is empty (package-less files)
+    let segments = diff
+        .strip_prefix('.')
+        .unwrap_or(diff)
+        .split('.')
+        .map(handler)
+        .collect::<Vec<_>>();
 
     quote::quote! {
         #(#segments::)*#ty
@@ -183,6 +191,14 @@ pub fn proto_attribute(field: &FieldDescriptor) -> TokenStream {
     }
 
     let options = field.options();
+
+    // proto2 repeated scalars are unpacked unless `[packed = true]` is set
+    let packed = if field.is_list() && resolve(field).is_some() && !descriptor::is_packed(field) {
+        quote! { , packed = false }
+    } else {
+        quote! {}
+    };
+
     if let Kind::Message(ty) = field.kind() {
         let cardinality = field.cardinality();
         if cardinality == Cardinality::Repeated && ty.is_map_entry() {
@@ -207,16 +223,18 @@ pub fn proto_attribute(field: &FieldDescriptor) -> TokenStream {
 
             match (key_resolved, value_resolved) {
                 (Some(key), Some(value)) => quote! {
-                    , scalar_key = #key, scalar_value = #value
+                    , key_scalar = #key, value_scalar = #value
                 },
                 (Some(key), None) => quote! {
-                    , scalar_key = #key
+                    , key_scalar = #key
                 },
                 (None, Some(value)) => quote! {
-                    , scalar_value = #value
+                    , value_scalar = #value
                 },
                 (None, None) => quote! {},
             }
+        } else if field.is_group() {
+            quote! { , group }
         } else {
             quote! {}
         }
@@ -224,13 +242,30 @@ pub fn proto_attribute(field: &FieldDescriptor) -> TokenStream {
         let resolved = resolve(field);
 
         if let Some(resolved) = resolved {
-            quote! { ,scalar = #resolved }
+            quote! { ,scalar = #resolved #packed }
         } else {
             quote! {}
         }
     }
 }
 
+/// the `utf8` argument of string fields, map fields keep the strict default
+pub fn utf8_attribute(ctx: &Generator, field: &FieldDescriptor) -> TokenStream {
+    match field.kind() {
+        Kind::String if !field.parent_message().is_map_entry() => {
+            ctx.utf8_validation(field.full_name()).attribute()
+        }
+        _ => quote! {},
+    }
+}
+
+/// string fields without validation are generated as bytes
+fn is_raw_string(ctx: &Generator, field: &FieldDescriptor) -> bool {
+    field.kind() == Kind::String
+        && !field.parent_message().is_map_entry()
+        && ctx.utf8_validation(field.full_name()) == Utf8Validation::None
+}
+
 pub fn field_type(ctx: &Generator, enclosed_type: &str, field: &FieldDescriptor) -> TokenStream {
     let options = field.options();
 
@@ -250,14 +285,27 @@ pub fn field_type(ctx: &Generator, enclosed_type: &str, field: &FieldDescriptor)
         Kind::Uint32 | Kind::Fixed32 => quote::quote!(u32),
         Kind::Uint64 | Kind::Fixed64 => quote::quote!(u64),
         Kind::Bool => quote::quote!(bool),
-        Kind::String => quote::quote!(String),
-        Kind::Bytes => quote::quote!(Vec<u8>),
+        Kind::String if !is_raw_string(ctx, field) => quote::quote!(String),
+        Kind::String | Kind::Bytes if ctx.use_bytes(field.full_name()) => {
+            let root = ctx.root_path();
+            quote::quote!(#root::bytes::Bytes)
+        }
+        Kind::String | Kind::Bytes => quote::quote!(Vec<u8>),
         Kind::Message(ty) => {
             if cardinality == Cardinality::Repeated && ty.is_map_entry() {
                 let key_ty = field_type(ctx, enclosed_type, &ty.map_entry_key_field());
                 let value_ty = field_type(ctx, enclosed_type, &ty.map_entry_value_field());
+
+                let map_type = match ext_ref(field.parent_pool(), MAP_TYPE, &options) {
+                    Some(Value::String(map_type)) => map_type
+                        .parse::<MapType>()
+                        .unwrap_or_else(|err| panic!("{}: {err}", field.full_name())),
+                    _ => ctx.map_type(field.full_name()),
+                };
+                let map_path = map_type.path(&ctx.root_path());
+
                 return quote::quote! {
-                    gin_tonic::fxhash::FxHashMap<#key_ty, #value_ty>
+                    #map_path<#key_ty, #value_ty>
                 };
             } else {
                 resolve_message(ctx, enclosed_type, ty.full_name())
@@ -266,7 +314,8 @@ pub fn field_type(ctx: &Generator, enclosed_type: &str, field: &FieldDescriptor)
         Kind::Enum(ty) => resolve_message(ctx, enclosed_type, ty.full_name()),
     };
 
-    let optional = field.field_descriptor_proto().proto3_optional();
+    let optional = field.field_descriptor_proto().proto3_optional()
+        || (is_proto2_optional(field) && default_value(ctx, enclosed_type, field).is_none());
     if optional {
         quote::quote! {
             Option<#field_type>
@@ -280,6 +329,107 @@ pub fn field_type(ctx: &Generator, enclosed_type: &str, field: &FieldDescriptor)
     }
 }
 
+/// proto2 `optional` fields have explicit presence, fields of oneofs and map entries are handled
+/// by their container
+fn is_proto2_optional(field: &FieldDescriptor) -> bool {
+    field.parent_file().syntax() == Syntax::Proto2
+        && field.cardinality() == Cardinality::Optional
+        && field.containing_oneof().is_none()
+        && !field.parent_message().is_map_entry()
+}
+
+/// proto3 fields without presence, their zero value is not encoded by other implementations
+fn is_proto3_implicit(field: &FieldDescriptor) -> bool {
+    field.parent_file().syntax() == Syntax::Proto3
+        && field.cardinality() == Cardinality::Optional
+        && !field.supports_presence()
+        && !field.parent_message().is_map_entry()
+        && !matches!(field.kind(), Kind::Message(_))
+}
+
+/// the Rust expression of a proto2 `[default = ...]` value or the zero value of proto3 fields
+/// matching [Generator::add_zero_default], fields with a default are generated as plain values
+/// which fall back to the default when absent
+pub fn default_value(
+    ctx: &Generator,
+    enclosed_type: &str,
+    field: &FieldDescriptor,
+) -> Option<TokenStream> {
+    let proto2_default =
+        is_proto2_optional(field) && field.field_descriptor_proto().default_value.is_some();
+    let zero_default = is_proto3_implicit(field) && ctx.use_zero_default(field.full_name());
+    if !proto2_default && !zero_default {
+        return None;
+    }
+
+    // the default can't be converted into a custom type
+    let options = field.options();
+    if ext_ref(field.parent_pool(), RUST_TYPE, &options).is_some() {
+        return None;
+    }
+
+    let bytes_default = |v: &[u8]| {
+        let v = proc_macro2::Literal::byte_string(v);
+        if ctx.use_bytes(field.full_name()) {
+            let root = ctx.root_path();
+            quote!(#root::bytes::Bytes::from_static(#v))
+        } else {
+            quote!(#v.to_vec())
+        }
+    };
+
+    let default = match (field.kind(), field.default_value()) {
+        (_, Value::Bool(v)) => quote!(#v),
+        (_, Value::I32(v)) => {
+            let v = proc_macro2::Literal::i32_suffixed(v);
+            quote!(#v)
+        }
+        (_, Value::I64(v)) => {
+            let v = proc_macro2::Literal::i64_suffixed(v);
+            quote!(#v)
+        }
+        (_, Value::U32(v)) => {
+            let v = proc_macro2::Literal::u32_suffixed(v);
+            quote!(#v)
+        }
+        (_, Value::U64(v)) => {
+            let v = proc_macro2::Literal::u64_suffixed(v);
+            quote!(#v)
+        }
+        (_, Value::F32(v)) if v.is_nan() => quote!(f32::NAN),
+        (_, Value::F32(v)) if v.is_infinite() && v > 0.0 => quote!(f32::INFINITY),
+        (_, Value::F32(v)) if v.is_infinite() => quote!(f32::NEG_INFINITY),
+        (_, Value::F32(v)) => {
+            let v = proc_macro2::Literal::f32_suffixed(v);
+            quote!(#v)
+        }
+        (_, Value::F64(v)) if v.is_nan() => quote!(f64::NAN),
+        (_, Value::F64(v)) if v.is_infinite() && v > 0.0 => quote!(f64::INFINITY),
+        (_, Value::F64(v)) if v.is_infinite() => quote!(f64::NEG_INFINITY),
+        (_, Value::F64(v)) => {
+            let v = proc_macro2::Literal::f64_suffixed(v);
+            quote!(#v)
+        }
+        (_, Value::String(v)) if !is_raw_string(ctx, field) => {
+            quote!(::std::string::String::from(#v))
+        }
+        (_, Value::String(v)) => bytes_default(v.as_bytes()),
+        (_, Value::Bytes(v)) => bytes_default(&v),
+        (Kind::Enum(ty), Value::EnumNumber(number)) => {
+            let value = ty.get_value(number)?;
+            let ty_name = case::convert(ty.name(), case::Case::Pascal);
+            // UNSPECIFIED values are not generated, the field stays optional
+            let variant = enums::variant_name(&ty_name, &value)?;
+            let variant = quote::format_ident!("{}", variant);
+            let path = resolve_message(ctx, enclosed_type, ty.full_name());
+            quote!(#path::#variant)
+        }
+        _ => return None,
+    };
+
+    Some(default)
+}
+
 // Checks whether a path pattern matches a given path.
 pub(crate) fn match_name(pattern: &str, path: &str) -> bool {
     // @HACK jeremy.barrow - 19 Jan 2024: Just a stupid hack for now.
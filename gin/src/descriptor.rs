@@ -0,0 +1,15 @@
+//! descriptor helpers shared by the code generator and [DynamicMessage](crate::reflection::DynamicMessage)
+
+use prost_reflect::{FieldDescriptor, Syntax};
+
+/// whether a repeated scalar field is encoded packed. `FieldDescriptor::is_packed` ignores the
+/// `[packed = ...]` option of protox compiled files, so the option is read from the descriptor
+/// and falls back to the default of the syntax
+pub(crate) fn is_packed(field: &FieldDescriptor) -> bool {
+    field
+        .field_descriptor_proto()
+        .options
+        .as_ref()
+        .and_then(|options| options.packed)
+        .unwrap_or(field.parent_file().syntax() == Syntax::Proto3)
+}
@@ -0,0 +1,230 @@
+//! `grpc.health.v1.Health` reporting the serving status set through a [HealthReporter]
+
+use std::{
+    collections::HashMap,
+    pin::Pin,
+    sync::{Arc, PoisonError, RwLock},
+    task::{Context, Poll, ready},
+};
+
+use tokio::sync::watch;
+use tokio_stream::{Stream, StreamExt, wrappers::WatchStream};
+use tonic::{codegen::BoxStream, server::NamedService};
+
+/// generated by `CompileConfig`, checked by `test::generator::health_service`
+#[rustfmt::skip]
+pub mod v1;
+
+pub use v1::health_check_response::ServingStatus;
+use v1::{
+    HealthCheckRequest, HealthCheckResponse,
+    health_server::{Health, HealthServer},
+};
+
+type Statuses = Arc<RwLock<HashMap<String, watch::Sender<ServingStatus>>>>;
+
+/// set the serving status of services, the server itself is reported by the empty service name
+///
+/// ```ignore
+/// let reporter = HealthReporter::new();
+/// reporter.set_serving::<EchoServer<Echo>>();
+///
+/// Server::builder()
+///     .add_service(reporter.service())
+///     .add_service(EchoServer::new(Echo))
+///     .serve(addr)
+///     .await?;
+/// ```
+#[derive(Clone, Debug)]
+pub struct HealthReporter {
+    statuses: Statuses,
+    /// changed whenever a service gets its first status, watchers of unknown services wait on it
+    registered: Arc<watch::Sender<()>>,
+}
+
+impl Default for HealthReporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HealthReporter {
+    /// the server itself is serving
+    pub fn new() -> Self {
+        let reporter = Self {
+            statuses: Default::default(),
+            registered: Arc::new(watch::Sender::new(())),
+        };
+        reporter.set_service_status("", ServingStatus::Serving);
+        reporter
+    }
+
+    pub fn set_serving<S: NamedService>(&self) {
+        self.set_service_status(S::NAME, ServingStatus::Serving);
+    }
+
+    pub fn set_not_serving<S: NamedService>(&self) {
+        self.set_service_status(S::NAME, ServingStatus::NotServing);
+    }
+
+    /// watchers are notified when the status changes
+    pub fn set_service_status(&self, service_name: impl AsRef<str>, status: ServingStatus) {
+        let service_name = service_name.as_ref();
+        let mut statuses = self
+            .statuses
+            .write()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        match statuses.get(service_name) {
+            Some(sender) => {
+                sender.send_if_modified(|current| {
+                    let modified = *current != status;
+                    *current = status;
+                    modified
+                });
+            }
+            None => {
+                statuses.insert(service_name.to_owned(), watch::Sender::new(status));
+                self.registered.send_modify(|_| ());
+            }
+        }
+    }
+
+    /// the service becomes unknown, `Check` fails with `NOT_FOUND` again
+    pub fn clear_service_status(&self, service_name: impl AsRef<str>) {
+        let statuses = self.statuses.read().unwrap_or_else(PoisonError::into_inner);
+        if let Some(sender) = statuses.get(service_name.as_ref()) {
+            sender.send_if_modified(|current| {
+                let modified = *current != ServingStatus::ServiceUnknown;
+                *current = ServingStatus::ServiceUnknown;
+                modified
+            });
+        }
+    }
+
+    /// current status, `None` if the service is unknown
+    pub fn service_status(&self, service_name: impl AsRef<str>) -> Option<ServingStatus> {
+        let statuses = self.statuses.read().unwrap_or_else(PoisonError::into_inner);
+        statuses
+            .get(service_name.as_ref())
+            .map(|sender| *sender.borrow())
+            .filter(|status| *status != ServingStatus::ServiceUnknown)
+    }
+
+    /// health service answering from this reporter
+    pub fn service(&self) -> HealthServer<HealthService> {
+        HealthServer::new(HealthService {
+            reporter: self.clone(),
+        })
+    }
+
+    fn receiver(&self, service_name: &str) -> Option<watch::Receiver<ServingStatus>> {
+        let statuses = self.statuses.read().unwrap_or_else(PoisonError::into_inner);
+        statuses.get(service_name).map(watch::Sender::subscribe)
+    }
+
+    /// service names come from the client, so unknown services are not stored. watching them
+    /// reports `SERVICE_UNKNOWN` and waits until the service gets a status
+    fn subscribe(&self, service_name: String) -> StatusStream {
+        let state = match self.receiver(&service_name) {
+            Some(receiver) => State::Known(WatchStream::new(receiver)),
+            None => State::Unknown {
+                // subscribed before the lookup on the first poll, a registration in between
+                // is not missed
+                registered: WatchStream::new(self.registered.subscribe()),
+                reported: false,
+            },
+        };
+
+        StatusStream {
+            reporter: self.clone(),
+            service_name,
+            state,
+        }
+    }
+}
+
+/// statuses of a watched service
+struct StatusStream {
+    reporter: HealthReporter,
+    service_name: String,
+    state: State,
+}
+
+enum State {
+    Known(WatchStream<ServingStatus>),
+    Unknown {
+        registered: WatchStream<()>,
+        reported: bool,
+    },
+}
+
+impl Stream for StatusStream {
+    type Item = ServingStatus;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            let this = &mut *self;
+            match &mut this.state {
+                State::Known(statuses) => return Pin::new(statuses).poll_next(cx),
+                State::Unknown {
+                    registered,
+                    reported,
+                } => {
+                    if ready!(Pin::new(registered).poll_next(cx)).is_none() {
+                        return Poll::Ready(None);
+                    }
+
+                    if let Some(receiver) = this.reporter.receiver(&this.service_name) {
+                        this.state = State::Known(WatchStream::new(receiver));
+                    } else if !*reported {
+                        *reported = true;
+                        return Poll::Ready(Some(ServingStatus::ServiceUnknown));
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// created by [HealthReporter::service]
+#[derive(Debug)]
+pub struct HealthService {
+    reporter: HealthReporter,
+}
+
+impl HealthService {
+    fn reporter(&self) -> &HealthReporter {
+        &self.reporter
+    }
+}
+
+#[tonic::codegen::async_trait]
+impl Health for HealthService {
+    type WatchStream = BoxStream<HealthCheckResponse>;
+
+    async fn check(
+        &self,
+        request: tonic::Request<HealthCheckRequest>,
+    ) -> Result<tonic::Response<HealthCheckResponse>, tonic::Status> {
+        let service_name = request.into_inner().service;
+        let status = self
+            .reporter()
+            .service_status(&service_name)
+            .ok_or_else(|| {
+                tonic::Status::not_found(format!("service {service_name} is unknown"))
+            })?;
+
+        Ok(tonic::Response::new(HealthCheckResponse { status }))
+    }
+
+    async fn watch(
+        &self,
+        request: tonic::Request<HealthCheckRequest>,
+    ) -> Result<tonic::Response<Self::WatchStream>, tonic::Status> {
+        let statuses = self.reporter().subscribe(request.into_inner().service);
+        let responses = statuses.map(|status| Ok(HealthCheckResponse { status }));
+
+        Ok(tonic::Response::new(Box::pin(responses)))
+    }
+}
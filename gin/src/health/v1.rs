@@ -0,0 +1,402 @@
+//!THIS FILE HAS BEEN GENERATED
+#[allow(unused_imports)]
+use crate::{Enumeration, Message, OneOf};
+/// Generated server implementations.
+pub mod health_server {
+    #![allow(
+        unused_variables,
+        dead_code,
+        missing_docs,
+        clippy::wildcard_imports,
+        clippy::let_unit_value,
+    )]
+    use tonic::codegen::*;
+    /// Generated trait containing gRPC methods that should be implemented for use with HealthServer.
+    #[async_trait]
+    pub trait Health: std::marker::Send + std::marker::Sync + 'static {
+        async fn check(
+            &self,
+            request: tonic::Request<super::HealthCheckRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::HealthCheckResponse>,
+            tonic::Status,
+        >;
+        /// Server streaming response type for the Watch method.
+        type WatchStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<super::HealthCheckResponse, tonic::Status>,
+            >
+            + std::marker::Send
+            + 'static;
+        async fn watch(
+            &self,
+            request: tonic::Request<super::HealthCheckRequest>,
+        ) -> std::result::Result<tonic::Response<Self::WatchStream>, tonic::Status>;
+    }
+    #[derive(Debug)]
+    pub struct HealthServer<T> {
+        inner: Arc<T>,
+        accept_compression_encodings: EnabledCompressionEncodings,
+        send_compression_encodings: EnabledCompressionEncodings,
+        max_decoding_message_size: Option<usize>,
+        max_encoding_message_size: Option<usize>,
+    }
+    impl<T> HealthServer<T> {
+        pub fn new(inner: T) -> Self {
+            Self::from_arc(Arc::new(inner))
+        }
+        pub fn from_arc(inner: Arc<T>) -> Self {
+            Self {
+                inner,
+                accept_compression_encodings: Default::default(),
+                send_compression_encodings: Default::default(),
+                max_decoding_message_size: None,
+                max_encoding_message_size: None,
+            }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> InterceptedService<Self, F>
+        where
+            F: tonic::service::Interceptor,
+        {
+            InterceptedService::new(Self::new(inner), interceptor)
+        }
+        /// Enable decompressing requests with the given encoding.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.accept_compression_encodings.enable(encoding);
+            self
+        }
+        /// Compress responses with the given encoding, if the client supports it.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.send_compression_encodings.enable(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.max_decoding_message_size = Some(limit);
+            self
+        }
+        /// Limits the maximum size of an encoded message.
+        ///
+        /// Default: `usize::MAX`
+        #[must_use]
+        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+            self.max_encoding_message_size = Some(limit);
+            self
+        }
+    }
+    impl<T, B> tonic::codegen::Service<http::Request<B>> for HealthServer<T>
+    where
+        T: Health,
+        B: Body + std::marker::Send + 'static,
+        B::Error: Into<StdError> + std::marker::Send + 'static,
+    {
+        type Response = http::Response<tonic::body::Body>;
+        type Error = std::convert::Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+        fn poll_ready(
+            &mut self,
+            _cx: &mut Context<'_>,
+        ) -> Poll<std::result::Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        fn call(&mut self, req: http::Request<B>) -> Self::Future {
+            match req.uri().path() {
+                "/grpc.health.v1.Health/Check" => {
+                    #[allow(non_camel_case_types)]
+                    struct CheckSvc<T: Health>(pub Arc<T>);
+                    impl<
+                        T: Health,
+                    > tonic::server::UnaryService<super::HealthCheckRequest>
+                    for CheckSvc<T> {
+                        type Response = super::HealthCheckResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::HealthCheckRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Health>::check(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = CheckSvc(inner);
+                        let codec = crate::GinCodec::<
+                            _,
+                            _,
+                            crate::DefaultStatusMapping,
+                        >::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/grpc.health.v1.Health/Watch" => {
+                    #[allow(non_camel_case_types)]
+                    struct WatchSvc<T: Health>(pub Arc<T>);
+                    impl<
+                        T: Health,
+                    > tonic::server::ServerStreamingService<super::HealthCheckRequest>
+                    for WatchSvc<T> {
+                        type Response = super::HealthCheckResponse;
+                        type ResponseStream = T::WatchStream;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::ResponseStream>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::HealthCheckRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Health>::watch(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = WatchSvc(inner);
+                        let codec = crate::GinCodec::<
+                            _,
+                            _,
+                            crate::DefaultStatusMapping,
+                        >::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                _ => {
+                    Box::pin(async move {
+                        let mut response = http::Response::new(
+                            tonic::body::Body::default(),
+                        );
+                        let headers = response.headers_mut();
+                        headers
+                            .insert(
+                                tonic::Status::GRPC_STATUS,
+                                (tonic::Code::Unimplemented as i32).into(),
+                            );
+                        headers
+                            .insert(
+                                http::header::CONTENT_TYPE,
+                                tonic::metadata::GRPC_CONTENT_TYPE,
+                            );
+                        Ok(response)
+                    })
+                }
+            }
+        }
+    }
+    impl<T> Clone for HealthServer<T> {
+        fn clone(&self) -> Self {
+            let inner = self.inner.clone();
+            Self {
+                inner,
+                accept_compression_encodings: self.accept_compression_encodings,
+                send_compression_encodings: self.send_compression_encodings,
+                max_decoding_message_size: self.max_decoding_message_size,
+                max_encoding_message_size: self.max_encoding_message_size,
+            }
+        }
+    }
+    /// Generated gRPC service name
+    pub const SERVICE_NAME: &str = "grpc.health.v1.Health";
+    impl<T> tonic::server::NamedService for HealthServer<T> {
+        const NAME: &'static str = SERVICE_NAME;
+    }
+}
+/// Generated client implementations.
+pub mod health_client {
+    #![allow(
+        unused_variables,
+        dead_code,
+        missing_docs,
+        clippy::wildcard_imports,
+        clippy::let_unit_value,
+    )]
+    use tonic::codegen::*;
+    use tonic::codegen::http::Uri;
+    #[derive(Debug, Clone)]
+    pub struct HealthClient<T> {
+        inner: tonic::client::Grpc<T>,
+    }
+    impl<T> HealthClient<T>
+    where
+        T: tonic::client::GrpcService<tonic::body::Body>,
+        T::Error: Into<StdError>,
+        T::ResponseBody: Body<Data = Bytes> + std::marker::Send + 'static,
+        <T::ResponseBody as Body>::Error: Into<StdError> + std::marker::Send,
+    {
+        pub fn new(inner: T) -> Self {
+            let inner = tonic::client::Grpc::new(inner);
+            Self { inner }
+        }
+        pub fn with_origin(inner: T, origin: Uri) -> Self {
+            let inner = tonic::client::Grpc::with_origin(inner, origin);
+            Self { inner }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> HealthClient<InterceptedService<T, F>>
+        where
+            F: tonic::service::Interceptor,
+            T::ResponseBody: Default,
+            T: tonic::codegen::Service<
+                http::Request<tonic::body::Body>,
+                Response = http::Response<
+                    <T as tonic::client::GrpcService<tonic::body::Body>>::ResponseBody,
+                >,
+            >,
+            <T as tonic::codegen::Service<
+                http::Request<tonic::body::Body>,
+            >>::Error: Into<StdError> + std::marker::Send + std::marker::Sync,
+        {
+            HealthClient::new(InterceptedService::new(inner, interceptor))
+        }
+        /// Compress requests with the given encoding.
+        ///
+        /// This requires the server to support it otherwise it might respond with an
+        /// error.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.inner = self.inner.send_compressed(encoding);
+            self
+        }
+        /// Enable decompressing responses.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.inner = self.inner.accept_compressed(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.inner = self.inner.max_decoding_message_size(limit);
+            self
+        }
+        /// Limits the maximum size of an encoded message.
+        ///
+        /// Default: `usize::MAX`
+        #[must_use]
+        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+            self.inner = self.inner.max_encoding_message_size(limit);
+            self
+        }
+        pub async fn check(
+            &mut self,
+            request: impl tonic::IntoRequest<super::HealthCheckRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::HealthCheckResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = crate::GinCodec::<_, _, crate::DefaultStatusMapping>::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/grpc.health.v1.Health/Check",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("grpc.health.v1.Health", "Check"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn watch(
+            &mut self,
+            request: impl tonic::IntoRequest<super::HealthCheckRequest>,
+        ) -> std::result::Result<
+            tonic::Response<tonic::codec::Streaming<super::HealthCheckResponse>>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = crate::GinCodec::<_, _, crate::DefaultStatusMapping>::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/grpc.health.v1.Health/Watch",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("grpc.health.v1.Health", "Watch"));
+            self.inner.server_streaming(req, path, codec).await
+        }
+    }
+}
+pub mod health_check_response;
+#[derive(Clone, Debug, Message)]
+#[gin(root = "crate")]
+pub struct HealthCheckRequest {
+    #[gin(
+        id = 1u32,
+        scalar = "string",
+        default = ":: std :: string :: String :: from (\"\")"
+    )]
+    pub service: String,
+}
+#[derive(Clone, Debug, Message)]
+#[gin(root = "crate")]
+pub struct HealthCheckResponse {
+    #[gin(
+        id = 1u32,
+        scalar = "int32",
+        default = "health_check_response :: ServingStatus :: Unknown"
+    )]
+    pub status: health_check_response::ServingStatus,
+}
+/// encoded `google.protobuf.FileDescriptorSet` of the package including its dependencies
+pub const FILE_DESCRIPTOR_SET: &[u8] = b"\n\xD8\x1A\n\x1Bgrpc/health/v1/health.proto\x12\x0Egrpc.health.v1\".\n\x12HealthCheckRequest\x12\x18\n\x07service\x18\x01 \x01(\tR\x07service\"\xB1\x01\n\x13HealthCheckResponse\x12I\n\x06status\x18\x01 \x01(\x0E21.grpc.health.v1.HealthCheckResponse.ServingStatusR\x06status\"O\n\rServingStatus\x12\x0B\n\x07UNKNOWN\x10\0\x12\x0B\n\x07SERVING\x10\x01\x12\x0F\n\x0BNOT_SERVING\x10\x02\x12\x13\n\x0FSERVICE_UNKNOWN\x10\x032\xAE\x01\n\x06Health\x12P\n\x05Check\x12\".grpc.health.v1.HealthCheckRequest\x1A#.grpc.health.v1.HealthCheckResponse\x12R\n\x05Watch\x12\".grpc.health.v1.HealthCheckRequest\x1A#.grpc.health.v1.HealthCheckResponse0\x01Ba\n\x11io.grpc.health.v1B\x0BHealthProtoP\x01Z,google.golang.org/grpc/health/grpc_health_v1\xAA\x02\x0EGrpc.Health.V1J\xA8\x16\n\x06\x12\x04\x11\0I\x01\n\x08\n\x01\x02\x12\x03\x13\0\x17\n\n\n\x02\x04\0\x12\x04\x1B\0\x1D\x01\n\n\n\x03\x04\0\x01\x12\x03\x1B\x08\x1A\n\x0B\n\x04\x04\0\x02\0\x12\x03\x1C\x02\x15\n\x0C\n\x05\x04\0\x02\0\x01\x12\x03\x1C\t\x10\n\x0C\n\x05\x04\0\x02\0\x03\x12\x03\x1C\x13\x14\n\x0C\n\x05\x04\0\x02\0\x05\x12\x03\x1C\x02\x08\n\n\n\x02\x04\x01\x12\x04\x1F\0'\x01\n\n\n\x03\x04\x01\x01\x12\x03\x1F\x08\x1B\n\x0B\n\x04\x04\x01\x02\0\x12\x03&\x02\x1B\n\x0C\n\x05\x04\x01\x02\0\x01\x12\x03&\x10\x16\n\x0C\n\x05\x04\x01\x02\0\x03\x12\x03&\x19\x1A\n\x0C\n\x05\x04\x01\x02\0\x06\x12\x03&\x02\x0F\n\x0C\n\x04\x04\x01\x04\0\x12\x04 \x02%\x03\n\x0C\n\x05\x04\x01\x04\0\x01\x12\x03 \x07\x14\n\r\n\x06\x04\x01\x04\0\x02\0\x12\x03!\x04\x10\n\x0E\n\x07\x04\x01\x04\0\x02\0\x01\x12\x03!\x04\x0B\n\x0E\n\x07\x04\x01\x04\0\x02\0\x02\x12\x03!\x0E\x0F\n\r\n\x06\x04\x01\x04\0\x02\x01\x12\x03\"\x04\x10\n\x0E\n\x07\x04\x01\x04\0\x02\x01\x01\x12\x03\"\x04\x0B\n\x0E\n\x07\x04\x01\x04\0\x02\x01\x02\x12\x03\"\x0E\x0F\n\r\n\x06\x04\x01\x04\0\x02\x02\x12\x03#\x04\x14\n\x0E\n\x07\x04\x01\x04\0\x02\x02\x01\x12\x03#\x04\x0F\n\x0E\n\x07\x04\x01\x04\0\x02\x02\x02\x12\x03#\x12\x13\n/\n\x06\x04\x01\x04\0\x02\x03\x12\x03$\x04\x18\"  Used only by the Watch method.\n\n\x0E\n\x07\x04\x01\x04\0\x02\x03\x01\x12\x03$\x04\x13\n\x0E\n\x07\x04\x01\x04\0\x02\x03\x02\x12\x03$\x16\x17\n\xC2\x01\n\x02\x06\0\x12\x04,\0I\x01\x1A\xB5\x01 Health is gRPC's mechanism for checking whether a server is able to handle\n RPCs. Its semantics are documented in\n https://github.com/grpc/grpc/blob/master/doc/health-checking.md.\n\n\n\n\x03\x06\0\x01\x12\x03,\x08\x0E\n\xC1\x03\n\x04\x06\0\x02\0\x12\x036\x02>\x1A\xB3\x03 Check gets the health of the specified service. If the requested service\n is unknown, the call will fail with status NOT_FOUND. If the caller does\n not specify a service name, the server should respond with its overall\n health status.\n\n Clients should set a deadline when calling Check, and can declare the\n server unhealthy if they do not receive a timely response.\n\n Check implementations should be idempotent and side effect free.\n\n\x0C\n\x05\x06\0\x02\0\x01\x12\x036\x06\x0B\n\x0C\n\x05\x06\0\x02\0\x02\x12\x036\x0C\x1E\n\x0C\n\x05\x06\0\x02\0\x03\x12\x036)<\n\xD2\x06\n\x04\x06\0\x02\x01\x12\x03H\x02E\x1A\xC4\x06 Performs a watch for the serving status of the requested service.\n The server will immediately send back a message indicating the current\n serving status.  It will then subsequently send a new message whenever\n the service's serving status changes.\n\n If the requested service is unknown when the call is received, the\n server will send a message setting the serving status to\n SERVICE_UNKNOWN but will *not* terminate the call.  If at some\n future point, the serving status of the service becomes known, the\n server will send a new message with the service's serving status.\n\n If the call terminates with status UNIMPLEMENTED, then clients\n should assume this method is not supported and should not call it.\n\n If the call fails with any other status (including OK), clients\n should retry the call with appropriate exponential backoff.\n\n\x0C\n\x05\x06\0\x02\x01\x01\x12\x03H\x06\x0B\n\x0C\n\x05\x06\0\x02\x01\x02\x12\x03H\x0C\x1E\n\x0C\n\x05\x06\0\x02\x01\x03\x12\x03H0C\n\x0C\n\x05\x06\0\x02\x01\x06\x12\x03H)/\n\x08\n\x01\x08\x12\x03\x15\0+\n\x08\n\x01\x08\x12\x03\x16\0C\n\x08\n\x01\x08\x12\x03\x17\0\"\n\x08\n\x01\x08\x12\x03\x18\0,\n\x08\n\x01\x08\x12\x03\x19\0*\n\t\n\x02\x08%\x12\x03\x15\0+\n\t\n\x02\x08\x0B\x12\x03\x16\0C\n\t\n\x02\x08\n\x12\x03\x17\0\"\n\t\n\x02\x08\x08\x12\x03\x18\0,\n\t\n\x02\x08\x01\x12\x03\x19\0*\n\xC6\x05\n\x01\x0C\x12\x03\x11\0\x122\xB7\x04 Copyright 2015 The gRPC Authors\n\n Licensed under the Apache License, Version 2.0 (the \"License\");\n you may not use this file except in compliance with the License.\n You may obtain a copy of the License at\n\n     http://www.apache.org/licenses/LICENSE-2.0\n\n Unless required by applicable law or agreed to in writing, software\n distributed under the License is distributed on an \"AS IS\" BASIS,\n WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.\n See the License for the specific language governing permissions and\n limitations under the License.\n2\x81\x01 The canonical version of this proto can be found at\n https://github.com/grpc/grpc-proto/blob/master/grpc/health/v1/health.proto\nb\x06proto3";
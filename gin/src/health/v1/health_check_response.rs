@@ -0,0 +1,15 @@
+//!THIS FILE HAS BEEN GENERATED
+#[allow(unused_imports)]
+use crate::{Enumeration, Message, OneOf};
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Enumeration)]
+#[gin(root = "crate")]
+pub enum ServingStatus {
+    #[gin(id = 0i32, name = "UNKNOWN")]
+    Unknown,
+    #[gin(id = 1i32, name = "SERVING")]
+    Serving,
+    #[gin(id = 2i32, name = "NOT_SERVING")]
+    NotServing,
+    #[gin(id = 3i32, name = "SERVICE_UNKNOWN")]
+    ServiceUnknown,
+}
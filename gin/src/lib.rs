@@ -4,23 +4,36 @@ mod codec;
 
 #[cfg(feature = "generator")]
 mod codegen;
+#[cfg(any(feature = "generator", feature = "reflection"))]
+mod descriptor;
+#[cfg(feature = "health")]
+pub mod health;
+#[cfg(feature = "reflection")]
+pub mod reflection;
 
 #[cfg(feature = "tonic")]
-pub use codec::GinCodec;
+pub use codec::{DefaultStatusMapping, GinCodec, StatusMapping};
 #[cfg(all(feature = "generator", feature = "internals"))]
 pub use codegen::Generator;
 #[cfg(feature = "generator")]
-pub use codegen::{CompileConfig, CompilerError};
+pub use codegen::{CompileConfig, CompilerError, MapType, Utf8Validation};
 pub use gin_tonic_core;
+#[cfg(feature = "bytes")]
+pub use gin_tonic_core::bytes;
+#[cfg(feature = "indexmap")]
+pub use gin_tonic_core::indexmap;
 pub use gin_tonic_core::{
-    Decode, Encode, Map, Message, PackableMarker, Packed, ProtoError, Scalar, Tag, Unpacked,
-    WIRE_TYPE_I32, WIRE_TYPE_I64, WIRE_TYPE_LENGTH_ENCODED, WIRE_TYPE_VARINT, decoder, encoder,
-    fxhash, scalars, types,
+    Decode, Encode, Extension, ExtensionSet, Map, Message, PackableMarker, Packed, ProtoError,
+    RawFields, RawMessageView, Scalar, Tag, Unpacked, WIRE_TYPE_END_GROUP, WIRE_TYPE_I32,
+    WIRE_TYPE_I64, WIRE_TYPE_LENGTH_ENCODED, WIRE_TYPE_START_GROUP, WIRE_TYPE_VARINT, decoder,
+    delimited, encoder, fxhash, scalars, types,
 };
 #[cfg(feature = "derive")]
 pub use gin_tonic_derive;
 #[cfg(feature = "derive")]
 pub use gin_tonic_derive::{Enumeration, Message, OneOf};
+#[cfg(feature = "reflection")]
+pub use prost_reflect;
 #[cfg(all(feature = "generator", feature = "internals"))]
 pub use protox;
 
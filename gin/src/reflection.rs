@@ -0,0 +1,60 @@
+//! descriptors of generated messages, see `CompileConfig::reflection`, and messages decoded
+//! from descriptors at runtime
+
+use std::sync::OnceLock;
+
+mod dynamic;
+#[cfg(all(feature = "derive", feature = "tonic"))]
+mod service;
+/// generated by `CompileConfig`, checked by `test::generator::reflection_service`
+#[cfg(all(feature = "derive", feature = "tonic"))]
+#[rustfmt::skip]
+pub mod v1;
+
+pub use dynamic::{DynamicMessage, MapKey, Value};
+pub use prost_reflect::{DescriptorPool, MessageDescriptor};
+#[cfg(all(feature = "derive", feature = "tonic"))]
+pub use service::ReflectionService;
+
+/// messages generated with `CompileConfig::reflection` expose their descriptor
+pub trait Descriptor {
+    /// fully qualified protobuf name, e.g. `package.v1.Message`
+    const FULL_NAME: &'static str;
+
+    fn descriptor() -> MessageDescriptor;
+}
+
+/// [DescriptorPool] of an embedded file descriptor set, decoded on first use
+pub struct LazyDescriptorPool {
+    file_descriptor_set: &'static [u8],
+    pool: OnceLock<DescriptorPool>,
+}
+
+impl LazyDescriptorPool {
+    pub const fn new(file_descriptor_set: &'static [u8]) -> Self {
+        Self {
+            file_descriptor_set,
+            pool: OnceLock::new(),
+        }
+    }
+
+    /// the encoded `google.protobuf.FileDescriptorSet`
+    pub fn file_descriptor_set(&self) -> &'static [u8] {
+        self.file_descriptor_set
+    }
+
+    /// panics if the file descriptor set is invalid, which does not happen for generated sets
+    pub fn get(&self) -> &DescriptorPool {
+        self.pool.get_or_init(|| {
+            DescriptorPool::decode(self.file_descriptor_set)
+                .expect("generated file descriptor sets are valid")
+        })
+    }
+
+    /// panics if the message is not part of the pool
+    pub fn message(&self, full_name: &str) -> MessageDescriptor {
+        self.get()
+            .get_message_by_name(full_name)
+            .unwrap_or_else(|| panic!("message {full_name} is not part of the descriptor pool"))
+    }
+}
@@ -0,0 +1,526 @@
+//! messages decoded from a [MessageDescriptor] at runtime, e.g. for types unknown at compile time
+
+use std::collections::BTreeMap;
+
+use gin_tonic_core::{
+    Decode, Encode, ExtensionSet, Message, ProtoError, Tag, WIRE_TYPE_END_GROUP, WIRE_TYPE_I32,
+    WIRE_TYPE_I64, WIRE_TYPE_LENGTH_ENCODED, WIRE_TYPE_START_GROUP, WIRE_TYPE_VARINT,
+    decoder::Decoder,
+    encoder::{Encoder, SizeHint},
+};
+use prost_reflect::{FieldDescriptor, Kind, MessageDescriptor};
+
+use crate::descriptor;
+
+/// value of a [DynamicMessage] field
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Bool(bool),
+    /// `int32`, `sint32` and `sfixed32`
+    I32(i32),
+    /// `int64`, `sint64` and `sfixed64`
+    I64(i64),
+    /// `uint32` and `fixed32`
+    U32(u32),
+    /// `uint64` and `fixed64`
+    U64(u64),
+    F32(f32),
+    F64(f64),
+    String(String),
+    Bytes(Vec<u8>),
+    /// number of an enum value, numbers unknown to the enum are kept
+    EnumNumber(i32),
+    Message(DynamicMessage),
+    List(Vec<Value>),
+    Map(BTreeMap<MapKey, Value>),
+}
+
+/// key of a map field
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MapKey {
+    Bool(bool),
+    I32(i32),
+    I64(i64),
+    U32(u32),
+    U64(u64),
+    String(String),
+}
+
+impl From<MapKey> for Value {
+    fn from(key: MapKey) -> Self {
+        match key {
+            MapKey::Bool(v) => Value::Bool(v),
+            MapKey::I32(v) => Value::I32(v),
+            MapKey::I64(v) => Value::I64(v),
+            MapKey::U32(v) => Value::U32(v),
+            MapKey::U64(v) => Value::U64(v),
+            MapKey::String(v) => Value::String(v),
+        }
+    }
+}
+
+impl TryFrom<Value> for MapKey {
+    type Error = ProtoError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Bool(v) => Ok(MapKey::Bool(v)),
+            Value::I32(v) => Ok(MapKey::I32(v)),
+            Value::I64(v) => Ok(MapKey::I64(v)),
+            Value::U32(v) => Ok(MapKey::U32(v)),
+            Value::U64(v) => Ok(MapKey::U64(v)),
+            Value::String(v) => Ok(MapKey::String(v)),
+            value => Err(ProtoError::Custom(format!(
+                "{value:?} is not a valid map key"
+            ))),
+        }
+    }
+}
+
+/// message described by a [MessageDescriptor] instead of a rust type
+///
+/// fields are decoded and encoded with [Decode] and [Encode] like derived messages, so both
+/// produce the same bytes. fields unknown to the descriptor, including extensions, are kept and
+/// written back when encoding
+///
+/// ```ignore
+/// let message = DynamicMessage::decode(EchoRequest::descriptor(), &mut Decoder::new(&buffer))?;
+/// assert_eq!(message.get("text"), Some(&Value::String("hello".into())));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct DynamicMessage {
+    descriptor: MessageDescriptor,
+    fields: BTreeMap<u32, Value>,
+    unknown_fields: ExtensionSet,
+}
+
+impl DynamicMessage {
+    /// message without any field set
+    pub fn new(descriptor: MessageDescriptor) -> Self {
+        Self {
+            descriptor,
+            fields: BTreeMap::new(),
+            unknown_fields: ExtensionSet::default(),
+        }
+    }
+
+    pub fn descriptor(&self) -> &MessageDescriptor {
+        &self.descriptor
+    }
+
+    pub fn decode(
+        descriptor: MessageDescriptor,
+        decoder: &mut impl Decode,
+    ) -> Result<Self, ProtoError> {
+        let mut message = Self::new(descriptor);
+        message.merge(decoder)?;
+        Ok(message)
+    }
+
+    /// decode fields into this message: scalars take the last value, nested messages merge and
+    /// repeated fields append
+    pub fn merge(&mut self, decoder: &mut impl Decode) -> Result<(), ProtoError> {
+        while !decoder.eof() {
+            let tag = decoder.decode_tag()?;
+            self.decode_field(tag, decoder)
+                .map_err(|err| self.field_error(err, tag.field_number()))?;
+        }
+        Ok(())
+    }
+
+    /// transcode a statically typed message described by `descriptor`
+    pub fn from_message(
+        descriptor: MessageDescriptor,
+        message: &impl Message,
+    ) -> Result<Self, ProtoError> {
+        let mut buffer = vec![0u8; message.message_size_hint()];
+        message.encode_message(&mut Encoder::new(&mut buffer));
+        Self::decode(descriptor, &mut Decoder::new(&buffer))
+    }
+
+    /// transcode into a statically typed message
+    pub fn to_message<M: Message>(&self) -> Result<M, ProtoError> {
+        M::decode_message(&mut Decoder::new(&self.encode_to_vec()))
+    }
+
+    pub fn encode(&self, encoder: &mut impl Encode) {
+        for (number, value) in &self.fields {
+            if let Some(field) = self.descriptor.get_field(*number) {
+                encode_field(&field, value, encoder);
+            }
+        }
+        self.unknown_fields.encode(encoder);
+    }
+
+    pub fn size_hint(&self) -> usize {
+        let mut hint = SizeHint::default();
+        self.encode(&mut hint);
+        hint.size()
+    }
+
+    pub fn encode_to_vec(&self) -> Vec<u8> {
+        let mut buffer = vec![0u8; self.size_hint()];
+        self.encode(&mut Encoder::new(&mut buffer));
+        buffer
+    }
+
+    /// value of a field, `None` if the field is not set
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        let field = self.descriptor.get_field_by_name(name)?;
+        self.fields.get(&field.number())
+    }
+
+    pub fn get_by_number(&self, number: u32) -> Option<&Value> {
+        self.fields.get(&number)
+    }
+
+    /// set a field, the value has to match the type of the field. other fields of the same oneof
+    /// are cleared
+    pub fn set(&mut self, name: &str, value: Value) -> Result<(), ProtoError> {
+        let field = self.descriptor.get_field_by_name(name).ok_or_else(|| {
+            ProtoError::Custom(format!("{} has no field {name}", self.descriptor.name()))
+        })?;
+        if !is_valid(&field, &value) {
+            return Err(ProtoError::Custom(format!(
+                "{value:?} is not a valid value for {}",
+                field.full_name()
+            )));
+        }
+
+        self.clear_oneof(&field);
+        self.fields.insert(field.number(), value);
+        Ok(())
+    }
+
+    /// unset a field, returns the previous value
+    pub fn clear(&mut self, name: &str) -> Option<Value> {
+        let field = self.descriptor.get_field_by_name(name)?;
+        self.fields.remove(&field.number())
+    }
+
+    /// fields which are set, ordered by field number
+    pub fn fields(&self) -> impl Iterator<Item = (FieldDescriptor, &Value)> {
+        self.fields.iter().filter_map(|(number, value)| {
+            self.descriptor
+                .get_field(*number)
+                .map(|field| (field, value))
+        })
+    }
+
+    /// fields not known to the descriptor, extensions are part of them
+    pub fn unknown_fields(&self) -> &ExtensionSet {
+        &self.unknown_fields
+    }
+
+    fn decode_field(&mut self, tag: Tag, decoder: &mut impl Decode) -> Result<(), ProtoError> {
+        let number = tag.field_number();
+        let Some(field) = self
+            .descriptor
+            .get_field(number)
+            .filter(|field| accepts_wire_type(field, tag.wire_type()))
+        else {
+            // like other implementations a wire type mismatch keeps the field as unknown
+            return self.unknown_fields.decode_field(tag, decoder);
+        };
+        let kind = field.kind();
+
+        if field.is_map() {
+            let Kind::Message(entry) = &kind else {
+                unreachable!("map entries are messages");
+            };
+            // like derived maps the entry doesn't count as nesting, its message values do
+            let mut entry = DynamicMessage::new(entry.clone());
+            let size = decoder.decode_uint32()? as usize;
+            entry.merge(&mut decoder.sub_decoder(size)?)?;
+            let key = entry
+                .fields
+                .remove(&1)
+                .unwrap_or_else(|| default_value(&entry.descriptor.map_entry_key_field()));
+            let value = entry
+                .fields
+                .remove(&2)
+                .unwrap_or_else(|| default_value(&entry.descriptor.map_entry_value_field()));
+
+            let map = match self
+                .fields
+                .entry(number)
+                .or_insert_with(|| Value::Map(BTreeMap::new()))
+            {
+                Value::Map(map) => map,
+                _ => unreachable!("values are validated when set"),
+            };
+            decoder.options().check_repeated(map.len())?;
+            map.insert(MapKey::try_from(key)?, value);
+        } else if field.is_list() {
+            let values = match self
+                .fields
+                .entry(number)
+                .or_insert_with(|| Value::List(Vec::new()))
+            {
+                Value::List(values) => values,
+                _ => unreachable!("values are validated when set"),
+            };
+
+            if tag.wire_type() == WIRE_TYPE_LENGTH_ENCODED && is_packable(&kind) {
+                let size = decoder.decode_uint32()? as usize;
+                decoder.options().check_length(size)?;
+                let mut packed = decoder.sub_decoder(size)?;
+                while !packed.eof() {
+                    packed.options().check_repeated(values.len())?;
+                    values.push(decode_value(&kind, tag, &mut packed)?);
+                }
+            } else {
+                decoder.options().check_repeated(values.len())?;
+                values.push(decode_value(&kind, tag, decoder)?);
+            }
+        } else {
+            self.clear_oneof(&field);
+            match self.fields.get_mut(&number) {
+                Some(Value::Message(message)) => message.merge_nested(tag, decoder)?,
+                _ => {
+                    let value = decode_value(&kind, tag, decoder)?;
+                    self.fields.insert(number, value);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// merge a length delimited message or a group, limited by [DecodeOptions::max_depth] like
+    /// nested messages of derived types
+    ///
+    /// [DecodeOptions::max_depth]: crate::decoder::DecodeOptions::max_depth
+    fn merge_nested(&mut self, tag: Tag, decoder: &mut impl Decode) -> Result<(), ProtoError> {
+        if tag.wire_type() == WIRE_TYPE_START_GROUP {
+            return decoder.nested(|decoder| {
+                loop {
+                    if decoder.eof() {
+                        return Err(ProtoError::UnexpectedEof);
+                    }
+                    let inner = decoder.decode_tag()?;
+                    if inner.wire_type() == WIRE_TYPE_END_GROUP
+                        && inner.field_number() == tag.field_number()
+                    {
+                        return Ok(());
+                    }
+                    self.decode_field(inner, decoder)
+                        .map_err(|err| self.field_error(err, inner.field_number()))?;
+                }
+            });
+        }
+
+        let size = decoder.decode_uint32()? as usize;
+        decoder.options().check_message_size(size)?;
+        decoder
+            .sub_decoder(size)?
+            .nested(|decoder| self.merge(decoder))
+    }
+
+    fn clear_oneof(&mut self, field: &FieldDescriptor) {
+        if let Some(oneof) = field.containing_oneof() {
+            for other in oneof.fields() {
+                if other.number() != field.number() {
+                    self.fields.remove(&other.number());
+                }
+            }
+        }
+    }
+
+    #[cold]
+    fn field_error(&self, err: ProtoError, number: u32) -> ProtoError {
+        match self.descriptor.get_field(number) {
+            Some(field) => err.in_field(self.descriptor.name(), field.name()),
+            None => err.in_field(self.descriptor.name(), &number.to_string()),
+        }
+    }
+}
+
+fn wire_type(field: &FieldDescriptor) -> u8 {
+    match field.kind() {
+        Kind::Double | Kind::Fixed64 | Kind::Sfixed64 => WIRE_TYPE_I64,
+        Kind::Float | Kind::Fixed32 | Kind::Sfixed32 => WIRE_TYPE_I32,
+        Kind::String | Kind::Bytes => WIRE_TYPE_LENGTH_ENCODED,
+        Kind::Message(_) if field.is_group() => WIRE_TYPE_START_GROUP,
+        Kind::Message(_) => WIRE_TYPE_LENGTH_ENCODED,
+        _ => WIRE_TYPE_VARINT,
+    }
+}
+
+fn is_packable(kind: &Kind) -> bool {
+    !matches!(kind, Kind::String | Kind::Bytes | Kind::Message(_))
+}
+
+fn accepts_wire_type(field: &FieldDescriptor, wire_type: u8) -> bool {
+    wire_type == self::wire_type(field)
+        || (field.is_list() && wire_type == WIRE_TYPE_LENGTH_ENCODED && is_packable(&field.kind()))
+}
+
+fn decode_value(kind: &Kind, tag: Tag, decoder: &mut impl Decode) -> Result<Value, ProtoError> {
+    Ok(match kind {
+        Kind::Double => Value::F64(decoder.decode_double()?),
+        Kind::Float => Value::F32(decoder.decode_float()?),
+        Kind::Int32 => Value::I32(decoder.decode_int32()?),
+        Kind::Int64 => Value::I64(decoder.decode_int64()?),
+        Kind::Uint32 => Value::U32(decoder.decode_uint32()?),
+        Kind::Uint64 => Value::U64(decoder.decode_uint64()?),
+        Kind::Sint32 => Value::I32(decoder.decode_sint32()?),
+        Kind::Sint64 => Value::I64(decoder.decode_sint64()?),
+        Kind::Fixed32 => Value::U32(decoder.decode_fixed32()?),
+        Kind::Fixed64 => Value::U64(decoder.decode_fixed64()?),
+        Kind::Sfixed32 => Value::I32(decoder.decode_sfixed32()?),
+        Kind::Sfixed64 => Value::I64(decoder.decode_sfixed64()?),
+        Kind::Bool => Value::Bool(decoder.decode_bool()?),
+        Kind::String => Value::String(decoder.decode_string()?),
+        Kind::Bytes => Value::Bytes(decoder.decode_bytes()?),
+        Kind::Enum(_) => Value::EnumNumber(decoder.decode_int32()?),
+        Kind::Message(descriptor) => {
+            let mut message = DynamicMessage::new(descriptor.clone());
+            message.merge_nested(tag, decoder)?;
+            Value::Message(message)
+        }
+    })
+}
+
+fn default_value(field: &FieldDescriptor) -> Value {
+    match field.kind() {
+        Kind::Double => Value::F64(0.0),
+        Kind::Float => Value::F32(0.0),
+        Kind::Int32 | Kind::Sint32 | Kind::Sfixed32 => Value::I32(0),
+        Kind::Int64 | Kind::Sint64 | Kind::Sfixed64 => Value::I64(0),
+        Kind::Uint32 | Kind::Fixed32 => Value::U32(0),
+        Kind::Uint64 | Kind::Fixed64 => Value::U64(0),
+        Kind::Bool => Value::Bool(false),
+        Kind::String => Value::String(String::new()),
+        Kind::Bytes => Value::Bytes(Vec::new()),
+        Kind::Enum(descriptor) => Value::EnumNumber(descriptor.default_value().number()),
+        Kind::Message(descriptor) => Value::Message(DynamicMessage::new(descriptor)),
+    }
+}
+
+fn matches_kind(kind: &Kind, value: &Value) -> bool {
+    match (kind, value) {
+        (Kind::Message(descriptor), Value::Message(message)) => message.descriptor == *descriptor,
+        (kind, value) => matches!(
+            (kind, value),
+            (Kind::Double, Value::F64(_))
+                | (Kind::Float, Value::F32(_))
+                | (Kind::Int32 | Kind::Sint32 | Kind::Sfixed32, Value::I32(_))
+                | (Kind::Int64 | Kind::Sint64 | Kind::Sfixed64, Value::I64(_))
+                | (Kind::Uint32 | Kind::Fixed32, Value::U32(_))
+                | (Kind::Uint64 | Kind::Fixed64, Value::U64(_))
+                | (Kind::Bool, Value::Bool(_))
+                | (Kind::String, Value::String(_))
+                | (Kind::Bytes, Value::Bytes(_))
+                | (Kind::Enum(_), Value::EnumNumber(_))
+        ),
+    }
+}
+
+fn is_valid(field: &FieldDescriptor, value: &Value) -> bool {
+    match value {
+        Value::List(values) => {
+            field.is_list()
+                && values
+                    .iter()
+                    .all(|value| matches_kind(&field.kind(), value))
+        }
+        Value::Map(map) => {
+            let Kind::Message(entry) = field.kind() else {
+                return false;
+            };
+            let key_kind = entry.map_entry_key_field().kind();
+            let value_kind = entry.map_entry_value_field().kind();
+            field.is_map()
+                && map.iter().all(|(key, value)| {
+                    matches_kind(&key_kind, &Value::from(key.clone()))
+                        && matches_kind(&value_kind, value)
+                })
+        }
+        value => !field.is_list() && matches_kind(&field.kind(), value),
+    }
+}
+
+fn encode_field(field: &FieldDescriptor, value: &Value, encoder: &mut impl Encode) {
+    let number = field.number();
+    match value {
+        Value::List(values) if is_packable(&field.kind()) && descriptor::is_packed(field) => {
+            if values.is_empty() {
+                return;
+            }
+            let kind = field.kind();
+            let mut hint = SizeHint::default();
+            for value in values {
+                encode_value(&kind, value, &mut hint);
+            }
+            encoder.encode_tag(Tag::from_parts(number, WIRE_TYPE_LENGTH_ENCODED));
+            encoder.encode_uint64(hint.size() as u64);
+            for value in values {
+                encode_value(&kind, value, encoder);
+            }
+        }
+        Value::List(values) => {
+            for value in values {
+                encode_tagged(field, value, encoder);
+            }
+        }
+        Value::Map(map) => {
+            let Kind::Message(entry) = field.kind() else {
+                return;
+            };
+            let key_field = entry.map_entry_key_field();
+            let value_field = entry.map_entry_value_field();
+            for (key, value) in map {
+                let key = Value::from(key.clone());
+                let mut hint = SizeHint::default();
+                encode_tagged(&key_field, &key, &mut hint);
+                encode_tagged(&value_field, value, &mut hint);
+
+                encoder.encode_tag(Tag::from_parts(number, WIRE_TYPE_LENGTH_ENCODED));
+                encoder.encode_uint64(hint.size() as u64);
+                encode_tagged(&key_field, &key, encoder);
+                encode_tagged(&value_field, value, encoder);
+            }
+        }
+        value => encode_tagged(field, value, encoder),
+    }
+}
+
+fn encode_tagged(field: &FieldDescriptor, value: &Value, encoder: &mut impl Encode) {
+    let wire_type = wire_type(field);
+    encoder.encode_tag(Tag::from_parts(field.number(), wire_type));
+    match value {
+        Value::Message(message) if wire_type == WIRE_TYPE_START_GROUP => {
+            message.encode(encoder);
+            encoder.encode_tag(Tag::from_parts(field.number(), WIRE_TYPE_END_GROUP));
+        }
+        value => encode_value(&field.kind(), value, encoder),
+    }
+}
+
+/// encode a value without its tag, values are validated when set
+fn encode_value(kind: &Kind, value: &Value, encoder: &mut impl Encode) {
+    match (kind, value) {
+        (Kind::Double, Value::F64(v)) => encoder.encode_double(*v),
+        (Kind::Float, Value::F32(v)) => encoder.encode_float(*v),
+        (Kind::Int32, Value::I32(v)) => encoder.encode_int32(*v),
+        (Kind::Sint32, Value::I32(v)) => encoder.encode_sint32(*v),
+        (Kind::Sfixed32, Value::I32(v)) => encoder.encode_sfixed32(*v),
+        (Kind::Int64, Value::I64(v)) => encoder.encode_int64(*v),
+        (Kind::Sint64, Value::I64(v)) => encoder.encode_sint64(*v),
+        (Kind::Sfixed64, Value::I64(v)) => encoder.encode_sfixed64(*v),
+        (Kind::Uint32, Value::U32(v)) => encoder.encode_uint32(*v),
+        (Kind::Fixed32, Value::U32(v)) => encoder.encode_fixed32(*v),
+        (Kind::Uint64, Value::U64(v)) => encoder.encode_uint64(*v),
+        (Kind::Fixed64, Value::U64(v)) => encoder.encode_fixed64(*v),
+        (Kind::Bool, Value::Bool(v)) => encoder.encode_bool(*v),
+        (Kind::String, Value::String(v)) => encoder.encode_str(v),
+        (Kind::Bytes, Value::Bytes(v)) => encoder.encode_bytes(v),
+        (Kind::Enum(_), Value::EnumNumber(v)) => encoder.encode_int32(*v),
+        (Kind::Message(_), Value::Message(message)) => {
+            encoder.encode_uint64(message.size_hint() as u64);
+            message.encode(encoder);
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,209 @@
+//! `grpc.reflection.v1.ServerReflection` answering from the descriptor sets emitted by
+//! `CompileConfig::file_descriptor_set`
+
+use std::collections::HashSet;
+
+use prost_reflect::{DescriptorError, DescriptorPool, FileDescriptor};
+use tonic::codegen::{BoxStream, tokio_stream::StreamExt};
+
+use super::v1::{
+    ErrorResponse, ExtensionNumberResponse, ExtensionRequest, FileDescriptorResponse,
+    ListServiceResponse, ServerReflectionRequest, ServerReflectionResponse, ServiceResponse,
+    server_reflection_request::MessageRequest,
+    server_reflection_response::MessageResponse,
+    server_reflection_server::{ServerReflection, ServerReflectionServer},
+};
+
+/// reflection service, e.g. for `grpcurl`
+///
+/// ```ignore
+/// let reflection = ReflectionService::new()
+///     .file_descriptor_set(proto::echo::FILE_DESCRIPTOR_SET)?
+///     .into_server();
+/// ```
+#[derive(Clone, Debug)]
+pub struct ReflectionService {
+    pool: DescriptorPool,
+}
+
+impl Default for ReflectionService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ReflectionService {
+    /// a reflection service that only knows about itself
+    pub fn new() -> Self {
+        let pool = DescriptorPool::decode(super::v1::FILE_DESCRIPTOR_SET)
+            .expect("reflection file descriptor set is valid");
+        Self { pool }
+    }
+
+    /// add an encoded `google.protobuf.FileDescriptorSet`, files already known are skipped
+    pub fn file_descriptor_set(mut self, bytes: &[u8]) -> Result<Self, DescriptorError> {
+        self.pool.decode_file_descriptor_set(bytes)?;
+        Ok(self)
+    }
+
+    /// add all files of a [DescriptorPool], files already known are skipped
+    pub fn descriptor_pool(mut self, pool: &DescriptorPool) -> Result<Self, DescriptorError> {
+        self.pool
+            .add_file_descriptor_protos(pool.file_descriptor_protos().cloned())?;
+        Ok(self)
+    }
+
+    /// the pool requests are answered from
+    pub fn pool(&self) -> &DescriptorPool {
+        &self.pool
+    }
+
+    pub fn into_server(self) -> ServerReflectionServer<Self> {
+        ServerReflectionServer::new(self)
+    }
+
+    fn handle(&self, request: ServerReflectionRequest) -> ServerReflectionResponse {
+        let message_response = match &request.message_request {
+            MessageRequest::FileByFilename(name) => self
+                .pool
+                .get_file_by_name(name)
+                .ok_or_else(|| tonic::Status::not_found(format!("file {name} not found"))),
+            MessageRequest::FileContainingSymbol(symbol) => self
+                .file_containing_symbol(symbol)
+                .ok_or_else(|| tonic::Status::not_found(format!("symbol {symbol} not found"))),
+            MessageRequest::FileContainingExtension(extension) => {
+                self.file_containing_extension(extension).ok_or_else(|| {
+                    tonic::Status::not_found(format!(
+                        "extension {} of {} not found",
+                        extension.extension_number, extension.containing_type
+                    ))
+                })
+            }
+            MessageRequest::AllExtensionNumbersOfType(name) => {
+                return self.response(request.clone(), self.extension_numbers(name));
+            }
+            MessageRequest::ListServices(_) => {
+                return self.response(request.clone(), Ok(self.list_services()));
+            }
+        }
+        .map(file_descriptor_response);
+
+        self.response(request, message_response)
+    }
+
+    fn response(
+        &self,
+        request: ServerReflectionRequest,
+        message_response: Result<MessageResponse, tonic::Status>,
+    ) -> ServerReflectionResponse {
+        let message_response = message_response.unwrap_or_else(|status| {
+            MessageResponse::ErrorResponse(ErrorResponse {
+                error_code: status.code() as i32,
+                error_message: status.message().to_owned(),
+            })
+        });
+
+        ServerReflectionResponse {
+            valid_host: request.host.clone(),
+            original_request: request,
+            message_response,
+        }
+    }
+
+    fn file_containing_symbol(&self, symbol: &str) -> Option<FileDescriptor> {
+        let pool = &self.pool;
+        let symbol = symbol.strip_prefix('.').unwrap_or(symbol);
+
+        pool.get_message_by_name(symbol)
+            .map(|message| message.parent_file())
+            .or_else(|| pool.get_enum_by_name(symbol).map(|e| e.parent_file()))
+            .or_else(|| pool.get_service_by_name(symbol).map(|s| s.parent_file()))
+            .or_else(|| pool.get_extension_by_name(symbol).map(|e| e.parent_file()))
+            .or_else(|| {
+                // methods and fields are resolved through their parent
+                let (parent, name) = symbol.rsplit_once('.')?;
+                pool.get_service_by_name(parent)
+                    .filter(|service| service.methods().any(|method| method.name() == name))
+                    .map(|service| service.parent_file())
+                    .or_else(|| {
+                        pool.get_message_by_name(parent)
+                            .filter(|message| message.get_field_by_name(name).is_some())
+                            .map(|message| message.parent_file())
+                    })
+            })
+    }
+
+    fn file_containing_extension(&self, extension: &ExtensionRequest) -> Option<FileDescriptor> {
+        let number = u32::try_from(extension.extension_number).ok()?;
+        self.pool
+            .get_message_by_name(&extension.containing_type)?
+            .extensions()
+            .find(|candidate| candidate.number() == number)
+            .map(|candidate| candidate.parent_file())
+    }
+
+    fn extension_numbers(&self, name: &str) -> Result<MessageResponse, tonic::Status> {
+        let message = self
+            .pool
+            .get_message_by_name(name)
+            .ok_or_else(|| tonic::Status::not_found(format!("message {name} not found")))?;
+
+        Ok(MessageResponse::AllExtensionNumbersResponse(
+            ExtensionNumberResponse {
+                base_type_name: message.full_name().to_owned(),
+                extension_number: message
+                    .extensions()
+                    .map(|extension| extension.number() as i32)
+                    .collect(),
+            },
+        ))
+    }
+
+    fn list_services(&self) -> MessageResponse {
+        MessageResponse::ListServicesResponse(ListServiceResponse {
+            service: self
+                .pool
+                .services()
+                .map(|service| ServiceResponse {
+                    name: service.full_name().to_owned(),
+                })
+                .collect(),
+        })
+    }
+}
+
+/// the requested file followed by its transitive dependencies
+fn file_descriptor_response(file: FileDescriptor) -> MessageResponse {
+    let mut seen = HashSet::new();
+    let mut pending = vec![file];
+    let mut file_descriptor_proto = vec![];
+
+    while let Some(file) = pending.pop() {
+        if !seen.insert(file.name().to_owned()) {
+            continue;
+        }
+        file_descriptor_proto.push(file.encode_to_vec());
+        pending.extend(file.dependencies());
+    }
+
+    MessageResponse::FileDescriptorResponse(FileDescriptorResponse {
+        file_descriptor_proto,
+    })
+}
+
+#[tonic::codegen::async_trait]
+impl ServerReflection for ReflectionService {
+    type ServerReflectionInfoStream = BoxStream<ServerReflectionResponse>;
+
+    async fn server_reflection_info(
+        &self,
+        request: tonic::Request<tonic::Streaming<ServerReflectionRequest>>,
+    ) -> Result<tonic::Response<Self::ServerReflectionInfoStream>, tonic::Status> {
+        let service = self.clone();
+        let responses = request
+            .into_inner()
+            .map(move |request| request.map(|request| service.handle(request)));
+
+        Ok(tonic::Response::new(Box::pin(responses)))
+    }
+}
@@ -0,0 +1,406 @@
+//!THIS FILE HAS BEEN GENERATED
+#[allow(unused_imports)]
+use crate::{Enumeration, Message, OneOf};
+/// Generated server implementations.
+pub mod server_reflection_server {
+    #![allow(
+        unused_variables,
+        dead_code,
+        missing_docs,
+        clippy::wildcard_imports,
+        clippy::let_unit_value,
+    )]
+    use tonic::codegen::*;
+    /// Generated trait containing gRPC methods that should be implemented for use with ServerReflectionServer.
+    #[async_trait]
+    pub trait ServerReflection: std::marker::Send + std::marker::Sync + 'static {
+        /// Server streaming response type for the ServerReflectionInfo method.
+        type ServerReflectionInfoStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<
+                    super::ServerReflectionResponse,
+                    tonic::Status,
+                >,
+            >
+            + std::marker::Send
+            + 'static;
+        async fn server_reflection_info(
+            &self,
+            request: tonic::Request<tonic::Streaming<super::ServerReflectionRequest>>,
+        ) -> std::result::Result<
+            tonic::Response<Self::ServerReflectionInfoStream>,
+            tonic::Status,
+        >;
+    }
+    #[derive(Debug)]
+    pub struct ServerReflectionServer<T> {
+        inner: Arc<T>,
+        accept_compression_encodings: EnabledCompressionEncodings,
+        send_compression_encodings: EnabledCompressionEncodings,
+        max_decoding_message_size: Option<usize>,
+        max_encoding_message_size: Option<usize>,
+    }
+    impl<T> ServerReflectionServer<T> {
+        pub fn new(inner: T) -> Self {
+            Self::from_arc(Arc::new(inner))
+        }
+        pub fn from_arc(inner: Arc<T>) -> Self {
+            Self {
+                inner,
+                accept_compression_encodings: Default::default(),
+                send_compression_encodings: Default::default(),
+                max_decoding_message_size: None,
+                max_encoding_message_size: None,
+            }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> InterceptedService<Self, F>
+        where
+            F: tonic::service::Interceptor,
+        {
+            InterceptedService::new(Self::new(inner), interceptor)
+        }
+        /// Enable decompressing requests with the given encoding.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.accept_compression_encodings.enable(encoding);
+            self
+        }
+        /// Compress responses with the given encoding, if the client supports it.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.send_compression_encodings.enable(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.max_decoding_message_size = Some(limit);
+            self
+        }
+        /// Limits the maximum size of an encoded message.
+        ///
+        /// Default: `usize::MAX`
+        #[must_use]
+        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+            self.max_encoding_message_size = Some(limit);
+            self
+        }
+    }
+    impl<T, B> tonic::codegen::Service<http::Request<B>> for ServerReflectionServer<T>
+    where
+        T: ServerReflection,
+        B: Body + std::marker::Send + 'static,
+        B::Error: Into<StdError> + std::marker::Send + 'static,
+    {
+        type Response = http::Response<tonic::body::Body>;
+        type Error = std::convert::Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+        fn poll_ready(
+            &mut self,
+            _cx: &mut Context<'_>,
+        ) -> Poll<std::result::Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        fn call(&mut self, req: http::Request<B>) -> Self::Future {
+            match req.uri().path() {
+                "/grpc.reflection.v1.ServerReflection/ServerReflectionInfo" => {
+                    #[allow(non_camel_case_types)]
+                    struct ServerReflectionInfoSvc<T: ServerReflection>(pub Arc<T>);
+                    impl<
+                        T: ServerReflection,
+                    > tonic::server::StreamingService<super::ServerReflectionRequest>
+                    for ServerReflectionInfoSvc<T> {
+                        type Response = super::ServerReflectionResponse;
+                        type ResponseStream = T::ServerReflectionInfoStream;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::ResponseStream>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                tonic::Streaming<super::ServerReflectionRequest>,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as ServerReflection>::server_reflection_info(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ServerReflectionInfoSvc(inner);
+                        let codec = crate::GinCodec::<
+                            _,
+                            _,
+                            crate::DefaultStatusMapping,
+                        >::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                _ => {
+                    Box::pin(async move {
+                        let mut response = http::Response::new(
+                            tonic::body::Body::default(),
+                        );
+                        let headers = response.headers_mut();
+                        headers
+                            .insert(
+                                tonic::Status::GRPC_STATUS,
+                                (tonic::Code::Unimplemented as i32).into(),
+                            );
+                        headers
+                            .insert(
+                                http::header::CONTENT_TYPE,
+                                tonic::metadata::GRPC_CONTENT_TYPE,
+                            );
+                        Ok(response)
+                    })
+                }
+            }
+        }
+    }
+    impl<T> Clone for ServerReflectionServer<T> {
+        fn clone(&self) -> Self {
+            let inner = self.inner.clone();
+            Self {
+                inner,
+                accept_compression_encodings: self.accept_compression_encodings,
+                send_compression_encodings: self.send_compression_encodings,
+                max_decoding_message_size: self.max_decoding_message_size,
+                max_encoding_message_size: self.max_encoding_message_size,
+            }
+        }
+    }
+    /// Generated gRPC service name
+    pub const SERVICE_NAME: &str = "grpc.reflection.v1.ServerReflection";
+    impl<T> tonic::server::NamedService for ServerReflectionServer<T> {
+        const NAME: &'static str = SERVICE_NAME;
+    }
+}
+/// Generated client implementations.
+pub mod server_reflection_client {
+    #![allow(
+        unused_variables,
+        dead_code,
+        missing_docs,
+        clippy::wildcard_imports,
+        clippy::let_unit_value,
+    )]
+    use tonic::codegen::*;
+    use tonic::codegen::http::Uri;
+    #[derive(Debug, Clone)]
+    pub struct ServerReflectionClient<T> {
+        inner: tonic::client::Grpc<T>,
+    }
+    impl<T> ServerReflectionClient<T>
+    where
+        T: tonic::client::GrpcService<tonic::body::Body>,
+        T::Error: Into<StdError>,
+        T::ResponseBody: Body<Data = Bytes> + std::marker::Send + 'static,
+        <T::ResponseBody as Body>::Error: Into<StdError> + std::marker::Send,
+    {
+        pub fn new(inner: T) -> Self {
+            let inner = tonic::client::Grpc::new(inner);
+            Self { inner }
+        }
+        pub fn with_origin(inner: T, origin: Uri) -> Self {
+            let inner = tonic::client::Grpc::with_origin(inner, origin);
+            Self { inner }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> ServerReflectionClient<InterceptedService<T, F>>
+        where
+            F: tonic::service::Interceptor,
+            T::ResponseBody: Default,
+            T: tonic::codegen::Service<
+                http::Request<tonic::body::Body>,
+                Response = http::Response<
+                    <T as tonic::client::GrpcService<tonic::body::Body>>::ResponseBody,
+                >,
+            >,
+            <T as tonic::codegen::Service<
+                http::Request<tonic::body::Body>,
+            >>::Error: Into<StdError> + std::marker::Send + std::marker::Sync,
+        {
+            ServerReflectionClient::new(InterceptedService::new(inner, interceptor))
+        }
+        /// Compress requests with the given encoding.
+        ///
+        /// This requires the server to support it otherwise it might respond with an
+        /// error.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.inner = self.inner.send_compressed(encoding);
+            self
+        }
+        /// Enable decompressing responses.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.inner = self.inner.accept_compressed(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.inner = self.inner.max_decoding_message_size(limit);
+            self
+        }
+        /// Limits the maximum size of an encoded message.
+        ///
+        /// Default: `usize::MAX`
+        #[must_use]
+        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+            self.inner = self.inner.max_encoding_message_size(limit);
+            self
+        }
+        pub async fn server_reflection_info(
+            &mut self,
+            request: impl tonic::IntoStreamingRequest<
+                Message = super::ServerReflectionRequest,
+            >,
+        ) -> std::result::Result<
+            tonic::Response<tonic::codec::Streaming<super::ServerReflectionResponse>>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = crate::GinCodec::<_, _, crate::DefaultStatusMapping>::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/grpc.reflection.v1.ServerReflection/ServerReflectionInfo",
+            );
+            let mut req = request.into_streaming_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "grpc.reflection.v1.ServerReflection",
+                        "ServerReflectionInfo",
+                    ),
+                );
+            self.inner.streaming(req, path, codec).await
+        }
+    }
+}
+pub mod server_reflection_request;
+pub mod server_reflection_response;
+#[derive(Clone, Debug, Message)]
+#[gin(root = "crate")]
+pub struct ServerReflectionRequest {
+    #[gin(
+        id = 1u32,
+        scalar = "string",
+        default = ":: std :: string :: String :: from (\"\")"
+    )]
+    pub host: String,
+    #[gin(id = 0, oneof)]
+    pub message_request: server_reflection_request::MessageRequest,
+}
+#[derive(Clone, Debug, Message)]
+#[gin(root = "crate")]
+pub struct ExtensionRequest {
+    #[gin(
+        id = 1u32,
+        scalar = "string",
+        default = ":: std :: string :: String :: from (\"\")"
+    )]
+    pub containing_type: String,
+    #[gin(id = 2u32, scalar = "int32", default = "0i32")]
+    pub extension_number: i32,
+}
+#[derive(Clone, Debug, Message)]
+#[gin(root = "crate")]
+pub struct ServerReflectionResponse {
+    #[gin(
+        id = 1u32,
+        scalar = "string",
+        default = ":: std :: string :: String :: from (\"\")"
+    )]
+    pub valid_host: String,
+    #[gin(id = 2u32)]
+    pub original_request: ServerReflectionRequest,
+    #[gin(id = 0, oneof)]
+    pub message_response: server_reflection_response::MessageResponse,
+}
+#[derive(Clone, Debug, Message)]
+#[gin(root = "crate")]
+pub struct FileDescriptorResponse {
+    #[gin(id = 1u32, scalar = "bytes")]
+    pub file_descriptor_proto: Vec<Vec<u8>>,
+}
+#[derive(Clone, Debug, Message)]
+#[gin(root = "crate")]
+pub struct ExtensionNumberResponse {
+    #[gin(
+        id = 1u32,
+        scalar = "string",
+        default = ":: std :: string :: String :: from (\"\")"
+    )]
+    pub base_type_name: String,
+    #[gin(id = 2u32, scalar = "int32")]
+    pub extension_number: Vec<i32>,
+}
+#[derive(Clone, Debug, Message)]
+#[gin(root = "crate")]
+pub struct ListServiceResponse {
+    #[gin(id = 1u32)]
+    pub service: Vec<ServiceResponse>,
+}
+#[derive(Clone, Debug, Message)]
+#[gin(root = "crate")]
+pub struct ServiceResponse {
+    #[gin(
+        id = 1u32,
+        scalar = "string",
+        default = ":: std :: string :: String :: from (\"\")"
+    )]
+    pub name: String,
+}
+#[derive(Clone, Debug, Message)]
+#[gin(root = "crate")]
+pub struct ErrorResponse {
+    #[gin(id = 1u32, scalar = "int32", default = "0i32")]
+    pub error_code: i32,
+    #[gin(
+        id = 2u32,
+        scalar = "string",
+        default = ":: std :: string :: String :: from (\"\")"
+    )]
+    pub error_message: String,
+}
+/// encoded `google.protobuf.FileDescriptorSet` of the package including its dependencies
+pub const FILE_DESCRIPTOR_SET: &[u8] = b"\n\xE4;\n#grpc/reflection/v1/reflection.proto\x12\x12grpc.reflection.v1\"\xF3\x02\n\x17ServerReflectionRequest\x12\x12\n\x04host\x18\x01 \x01(\tR\x04host\x12*\n\x10file_by_filename\x18\x03 \x01(\tH\0R\x0EfileByFilename\x126\n\x16file_containing_symbol\x18\x04 \x01(\tH\0R\x14fileContainingSymbol\x12b\n\x19file_containing_extension\x18\x05 \x01(\x0B2$.grpc.reflection.v1.ExtensionRequestH\0R\x17fileContainingExtension\x12B\n\x1Dall_extension_numbers_of_type\x18\x06 \x01(\tH\0R\x19allExtensionNumbersOfType\x12%\n\rlist_services\x18\x07 \x01(\tH\0R\x0ClistServicesB\x11\n\x0Fmessage_request\"f\n\x10ExtensionRequest\x12'\n\x0Fcontaining_type\x18\x01 \x01(\tR\x0EcontainingType\x12)\n\x10extension_number\x18\x02 \x01(\x05R\x0FextensionNumber\"\xAE\x04\n\x18ServerReflectionResponse\x12\x1D\n\nvalid_host\x18\x01 \x01(\tR\tvalidHost\x12V\n\x10original_request\x18\x02 \x01(\x0B2+.grpc.reflection.v1.ServerReflectionRequestR\x0ForiginalRequest\x12f\n\x18file_descriptor_response\x18\x04 \x01(\x0B2*.grpc.reflection.v1.FileDescriptorResponseH\0R\x16fileDescriptorResponse\x12r\n\x1Eall_extension_numbers_response\x18\x05 \x01(\x0B2+.grpc.reflection.v1.ExtensionNumberResponseH\0R\x1BallExtensionNumbersResponse\x12_\n\x16list_services_response\x18\x06 \x01(\x0B2'.grpc.reflection.v1.ListServiceResponseH\0R\x14listServicesResponse\x12J\n\x0Eerror_response\x18\x07 \x01(\x0B2!.grpc.reflection.v1.ErrorResponseH\0R\rerrorResponseB\x12\n\x10message_response\"L\n\x16FileDescriptorResponse\x122\n\x15file_descriptor_proto\x18\x01 \x03(\x0CR\x13fileDescriptorProto\"j\n\x17ExtensionNumberResponse\x12$\n\x0Ebase_type_name\x18\x01 \x01(\tR\x0CbaseTypeName\x12)\n\x10extension_number\x18\x02 \x03(\x05R\x0FextensionNumber\"T\n\x13ListServiceResponse\x12=\n\x07service\x18\x01 \x03(\x0B2#.grpc.reflection.v1.ServiceResponseR\x07service\"%\n\x0FServiceResponse\x12\x12\n\x04name\x18\x01 \x01(\tR\x04name\"S\n\rErrorResponse\x12\x1D\n\nerror_code\x18\x01 \x01(\x05R\terrorCode\x12#\n\rerror_message\x18\x02 \x01(\tR\x0CerrorMessage2\x89\x01\n\x10ServerReflection\x12u\n\x14ServerReflectionInfo\x12+.grpc.reflection.v1.ServerReflectionRequest\x1A,.grpc.reflection.v1.ServerReflectionResponse(\x010\x01Bf\n\x15io.grpc.reflection.v1B\x15ServerReflectionProtoP\x01Z4google.golang.org/grpc/reflection/grpc_reflection_v1J\x91.\n\x07\x12\x05\x15\0\x91\x01\x01\n\x08\n\x01\x02\x12\x03\x17\0\x1B\nV\n\x02\x04\0\x12\x04&\0F\x01\x1AJ The message sent by the client when calling ServerReflectionInfo method.\n\n\n\n\x03\x04\0\x01\x12\x03&\x08\x1F\n\x0B\n\x04\x04\0\x02\0\x12\x03'\x02\x12\n\x0C\n\x05\x04\0\x02\0\x01\x12\x03'\t\r\n\x0C\n\x05\x04\0\x02\0\x03\x12\x03'\x10\x11\n\x0C\n\x05\x04\0\x02\0\x05\x12\x03'\x02\x08\n2\n\x04\x04\0\x02\x01\x12\x03-\x04 \x1A% Find a proto file by the file name.\n\n\x0C\n\x05\x04\0\x02\x01\x01\x12\x03-\x0B\x1B\n\x0C\n\x05\x04\0\x02\x01\x03\x12\x03-\x1E\x1F\n\x0C\n\x05\x04\0\x02\x01\x05\x12\x03-\x04\n\n\xC8\x01\n\x04\x04\0\x02\x02\x12\x032\x04&\x1A\xBA\x01 Find the proto file that declares the given fully-qualified symbol name.\n This field should be a fully-qualified symbol name\n (e.g. <package>.<service>[.<method>] or <package>.<type>).\n\n\x0C\n\x05\x04\0\x02\x02\x01\x12\x032\x0B!\n\x0C\n\x05\x04\0\x02\x02\x03\x12\x032$%\n\x0C\n\x05\x04\0\x02\x02\x05\x12\x032\x04\n\n|\n\x04\x04\0\x02\x03\x12\x036\x043\x1Ao Find the proto file which defines an extension extending the given\n message type with the given field number.\n\n\x0C\n\x05\x04\0\x02\x03\x01\x12\x036\x15.\n\x0C\n\x05\x04\0\x02\x03\x03\x12\x03612\n\x0C\n\x05\x04\0\x02\x03\x06\x12\x036\x04\x14\n\xEE\x03\n\x04\x04\0\x02\x04\x12\x03@\x04-\x1A\xE0\x03 Finds the tag numbers used by all known extensions of the given message\n type, and appends them to ExtensionNumberResponse in an undefined order.\n Its corresponding method is best-effort: it's not guaranteed that the\n reflection service will implement this method, and it's not guaranteed\n that this method will provide all extensions. Returns\n StatusCode::UNIMPLEMENTED if it's not implemented.\n This field should be a fully-qualified type name. The format is\n <package>.<type>\n\n\x0C\n\x05\x04\0\x02\x04\x01\x12\x03@\x0B(\n\x0C\n\x05\x04\0\x02\x04\x03\x12\x03@+,\n\x0C\n\x05\x04\0\x02\x04\x05\x12\x03@\x04\n\n\\\n\x04\x04\0\x02\x05\x12\x03D\x04\x1D\x1AO List the full names of registered services. The content will not be\n checked.\n\n\x0C\n\x05\x04\0\x02\x05\x01\x12\x03D\x0B\x18\n\x0C\n\x05\x04\0\x02\x05\x03\x12\x03D\x1B\x1C\n\x0C\n\x05\x04\0\x02\x05\x05\x12\x03D\x04\n\n\xDF\x01\n\x04\x04\0\x08\0\x12\x04+\x02E\x03\x1A\xD0\x01 To use reflection service, the client should set one of the following\n fields in message_request. The server distinguishes requests by their\n defined field and then handles them using corresponding methods.\n\n\x0C\n\x05\x04\0\x08\0\x01\x12\x03+\x08\x17\no\n\x02\x04\x01\x12\x04J\0N\x01\x1Ac The type name and extension number sent by the client when requesting\n file_containing_extension.\n\n\n\n\x03\x04\x01\x01\x12\x03J\x08\x18\nO\n\x04\x04\x01\x02\0\x12\x03L\x02\x1D\x1AB Fully-qualified type name. The format should be <package>.<type>\n\n\x0C\n\x05\x04\x01\x02\0\x01\x12\x03L\t\x18\n\x0C\n\x05\x04\x01\x02\0\x03\x12\x03L\x1B\x1C\n\x0C\n\x05\x04\x01\x02\0\x05\x12\x03L\x02\x08\n\x0B\n\x04\x04\x01\x02\x01\x12\x03M\x02\x1D\n\x0C\n\x05\x04\x01\x02\x01\x01\x12\x03M\x08\x18\n\x0C\n\x05\x04\x01\x02\x01\x03\x12\x03M\x1B\x1C\n\x0C\n\x05\x04\x01\x02\x01\x05\x12\x03M\x02\x07\nS\n\x02\x04\x02\x12\x04Q\0h\x01\x1AG The message sent by the server to answer ServerReflectionInfo method.\n\n\n\n\x03\x04\x02\x01\x12\x03Q\x08 \n\x0B\n\x04\x04\x02\x02\0\x12\x03R\x02\x18\n\x0C\n\x05\x04\x02\x02\0\x01\x12\x03R\t\x13\n\x0C\n\x05\x04\x02\x02\0\x03\x12\x03R\x16\x17\n\x0C\n\x05\x04\x02\x02\0\x05\x12\x03R\x02\x08\n\x0B\n\x04\x04\x02\x02\x01\x12\x03S\x02/\n\x0C\n\x05\x04\x02\x02\x01\x01\x12\x03S\x1A*\n\x0C\n\x05\x04\x02\x02\x01\x03\x12\x03S-.\n\x0C\n\x05\x04\x02\x02\x01\x06\x12\x03S\x02\x19\n\xB1\x03\n\x04\x04\x02\x02\x02\x12\x03]\x048\x1A\xA3\x03 This message is used to answer file_by_filename, file_containing_symbol,\n file_containing_extension requests with transitive dependencies.\n As the repeated label is not allowed in oneof fields, we use a\n FileDescriptorResponse message to encapsulate the repeated fields.\n The reflection service is allowed to avoid sending FileDescriptorProtos\n that were previously sent in response to earlier requests in the stream.\n\n\x0C\n\x05\x04\x02\x02\x02\x01\x12\x03]\x1B3\n\x0C\n\x05\x04\x02\x02\x02\x03\x12\x03]67\n\x0C\n\x05\x04\x02\x02\x02\x06\x12\x03]\x04\x1A\nU\n\x04\x04\x02\x02\x03\x12\x03`\x04?\x1AH This message is used to answer all_extension_numbers_of_type requests.\n\n\x0C\n\x05\x04\x02\x02\x03\x01\x12\x03`\x1C:\n\x0C\n\x05\x04\x02\x02\x03\x03\x12\x03`=>\n\x0C\n\x05\x04\x02\x02\x03\x06\x12\x03`\x04\x1B\nE\n\x04\x04\x02\x02\x04\x12\x03c\x043\x1A8 This message is used to answer list_services requests.\n\n\x0C\n\x05\x04\x02\x02\x04\x01\x12\x03c\x18.\n\x0C\n\x05\x04\x02\x02\x04\x03\x12\x03c12\n\x0C\n\x05\x04\x02\x02\x04\x06\x12\x03c\x04\x17\n9\n\x04\x04\x02\x02\x05\x12\x03f\x04%\x1A, This message is used when an error occurs.\n\n\x0C\n\x05\x04\x02\x02\x05\x01\x12\x03f\x12 \n\x0C\n\x05\x04\x02\x02\x05\x03\x12\x03f#$\n\x0C\n\x05\x04\x02\x02\x05\x06\x12\x03f\x04\x11\nm\n\x04\x04\x02\x08\0\x12\x04V\x02g\x03\x1A_ The server sets one of the following fields according to the message_request\n in the request.\n\n\x0C\n\x05\x04\x02\x08\0\x01\x12\x03V\x08\x18\n\xA7\x01\n\x02\x04\x03\x12\x04m\0r\x01\x1A\x9A\x01 Serialized FileDescriptorProto messages sent by the server answering\n a file_by_filename, file_containing_symbol, or file_containing_extension\n request.\n\n\n\n\x03\x04\x03\x01\x12\x03m\x08\x1E\n\xB2\x01\n\x04\x04\x03\x02\0\x12\x03q\x02+\x1A\xA4\x01 Serialized FileDescriptorProto messages. We avoid taking a dependency on\n descriptor.proto, which uses proto2 only features, by making them opaque\n bytes instead.\n\n\x0C\n\x05\x04\x03\x02\0\x01\x12\x03q\x11&\n\x0C\n\x05\x04\x03\x02\0\x03\x12\x03q)*\n\x0C\n\x05\x04\x03\x02\0\x04\x12\x03q\x02\n\n\x0C\n\x05\x04\x03\x02\0\x05\x12\x03q\x0B\x10\nn\n\x02\x04\x04\x12\x04v\0{\x01\x1Ab A list of extension numbers sent by the server answering\n all_extension_numbers_of_type request.\n\n\n\n\x03\x04\x04\x01\x12\x03v\x08\x1F\nf\n\x04\x04\x04\x02\0\x12\x03y\x02\x1C\x1AY Full name of the base type, including the package name. The format\n is <package>.<type>\n\n\x0C\n\x05\x04\x04\x02\0\x01\x12\x03y\t\x17\n\x0C\n\x05\x04\x04\x02\0\x03\x12\x03y\x1A\x1B\n\x0C\n\x05\x04\x04\x02\0\x05\x12\x03y\x02\x08\n\x0B\n\x04\x04\x04\x02\x01\x12\x03z\x02&\n\x0C\n\x05\x04\x04\x02\x01\x01\x12\x03z\x11!\n\x0C\n\x05\x04\x04\x02\x01\x03\x12\x03z$%\n\x0C\n\x05\x04\x04\x02\x01\x04\x12\x03z\x02\n\n\x0C\n\x05\x04\x04\x02\x01\x05\x12\x03z\x0B\x10\n\\\n\x02\x04\x05\x12\x05~\0\x82\x01\x01\x1AO A list of ServiceResponse sent by the server answering list_services request.\n\n\n\n\x03\x04\x05\x01\x12\x03~\x08\x1B\n\x84\x01\n\x04\x04\x05\x02\0\x12\x04\x81\x01\x02'\x1Av The information of each service may be expanded in the future, so we use\n ServiceResponse message to encapsulate it.\n\n\r\n\x05\x04\x05\x02\0\x01\x12\x04\x81\x01\x1B\"\n\r\n\x05\x04\x05\x02\0\x03\x12\x04\x81\x01%&\n\r\n\x05\x04\x05\x02\0\x04\x12\x04\x81\x01\x02\n\n\r\n\x05\x04\x05\x02\0\x06\x12\x04\x81\x01\x0B\x1A\nq\n\x02\x04\x06\x12\x06\x86\x01\0\x8A\x01\x01\x1Ac The information of a single service used by ListServiceResponse to answer\n list_services request.\n\n\x0B\n\x03\x04\x06\x01\x12\x04\x86\x01\x08\x17\nq\n\x04\x04\x06\x02\0\x12\x04\x89\x01\x02\x12\x1Ac Full name of a registered service, including its package name. The format\n is <package>.<service>\n\n\r\n\x05\x04\x06\x02\0\x01\x12\x04\x89\x01\t\r\n\r\n\x05\x04\x06\x02\0\x03\x12\x04\x89\x01\x10\x11\n\r\n\x05\x04\x06\x02\0\x05\x12\x04\x89\x01\x02\x08\nY\n\x02\x04\x07\x12\x06\x8D\x01\0\x91\x01\x01\x1AK The error code and error message sent by the server when an error occurs.\n\n\x0B\n\x03\x04\x07\x01\x12\x04\x8D\x01\x08\x15\nL\n\x04\x04\x07\x02\0\x12\x04\x8F\x01\x02\x17\x1A> This field uses the error codes defined in grpc::StatusCode.\n\n\r\n\x05\x04\x07\x02\0\x01\x12\x04\x8F\x01\x08\x12\n\r\n\x05\x04\x07\x02\0\x03\x12\x04\x8F\x01\x15\x16\n\r\n\x05\x04\x07\x02\0\x05\x12\x04\x8F\x01\x02\x07\n\x0C\n\x04\x04\x07\x02\x01\x12\x04\x90\x01\x02\x1B\n\r\n\x05\x04\x07\x02\x01\x01\x12\x04\x90\x01\t\x16\n\r\n\x05\x04\x07\x02\x01\x03\x12\x04\x90\x01\x19\x1A\n\r\n\x05\x04\x07\x02\x01\x05\x12\x04\x90\x01\x02\x08\n\n\n\x02\x06\0\x12\x04\x1E\0#\x01\n\n\n\x03\x06\0\x01\x12\x03\x1E\x08\x18\n\x85\x01\n\x04\x06\0\x02\0\x12\x04!\x02\"0\x1Aw The reflection service is structured as a bidirectional stream, ensuring\n all related requests go to a single server.\n\n\x0C\n\x05\x06\0\x02\0\x01\x12\x03!\x06\x1A\n\x0C\n\x05\x06\0\x02\0\x02\x12\x03!\"9\n\x0C\n\x05\x06\0\x02\0\x03\x12\x03\"\x16.\n\x0C\n\x05\x06\0\x02\0\x05\x12\x03!\x1B!\n\x0C\n\x05\x06\0\x02\0\x06\x12\x03\"\x0F\x15\n\x08\n\x01\x08\x12\x03\x19\0K\n\x08\n\x01\x08\x12\x03\x1A\0\"\n\x08\n\x01\x08\x12\x03\x1B\0.\n\x08\n\x01\x08\x12\x03\x1C\x006\n\t\n\x02\x08\x0B\x12\x03\x19\0K\n\t\n\x02\x08\n\x12\x03\x1A\0\"\n\t\n\x02\x08\x01\x12\x03\x1B\0.\n\t\n\x02\x08\x08\x12\x03\x1C\x006\n\x87\x07\n\x01\x0C\x12\x03\x15\0\x122\xB7\x04 Copyright 2016 The gRPC Authors\n\n Licensed under the Apache License, Version 2.0 (the \"License\");\n you may not use this file except in compliance with the License.\n You may obtain a copy of the License at\n\n     http://www.apache.org/licenses/LICENSE-2.0\n\n Unless required by applicable law or agreed to in writing, software\n distributed under the License is distributed on an \"AS IS\" BASIS,\n WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.\n See the License for the specific language governing permissions and\n limitations under the License.\n2\xC2\x02 Service exported by server reflection.  A more complete description of how\n server reflection works can be found at\n https://github.com/grpc/grpc/blob/master/doc/server-reflection.md\n\n The canonical version of this proto can be found at\n https://github.com/grpc/grpc-proto/blob/master/grpc/reflection/v1/reflection.proto\nb\x06proto3";
@@ -0,0 +1,17 @@
+//!THIS FILE HAS BEEN GENERATED
+#[allow(unused_imports)]
+use crate::{Enumeration, Message, OneOf};
+#[derive(Clone, Debug, OneOf)]
+#[gin(root = "crate")]
+pub enum MessageRequest {
+    #[gin(id = 3u32, scalar = "string")]
+    FileByFilename(String),
+    #[gin(id = 4u32, scalar = "string")]
+    FileContainingSymbol(String),
+    #[gin(id = 5u32)]
+    FileContainingExtension(super::ExtensionRequest),
+    #[gin(id = 6u32, scalar = "string")]
+    AllExtensionNumbersOfType(String),
+    #[gin(id = 7u32, scalar = "string")]
+    ListServices(String),
+}
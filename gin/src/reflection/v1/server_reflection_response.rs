@@ -0,0 +1,15 @@
+//!THIS FILE HAS BEEN GENERATED
+#[allow(unused_imports)]
+use crate::{Enumeration, Message, OneOf};
+#[derive(Clone, Debug, OneOf)]
+#[gin(root = "crate")]
+pub enum MessageResponse {
+    #[gin(id = 4u32)]
+    FileDescriptorResponse(super::FileDescriptorResponse),
+    #[gin(id = 5u32)]
+    AllExtensionNumbersResponse(super::ExtensionNumberResponse),
+    #[gin(id = 6u32)]
+    ListServicesResponse(super::ListServiceResponse),
+    #[gin(id = 7u32)]
+    ErrorResponse(super::ErrorResponse),
+}
@@ -262,6 +262,300 @@ mod scalars {
             assert_eq!(test, read)
         }
     }
+
+    mod packed_unpacked {
+        use gin_tonic_core::{Message, decoder::Decoder, encoder::Encoder};
+
+        #[derive(Default, Debug, PartialEq, gin_tonic_derive::Message)]
+        #[gin(root = "crate")]
+        struct Packed {
+            #[gin(id = 1, scalar = "int32")]
+            int32: Vec<i32>,
+            #[gin(id = 2, scalar = "sint64")]
+            sint64: Vec<i64>,
+            #[gin(id = 3, scalar = "fixed32")]
+            fixed32: Vec<u32>,
+            #[gin(id = 4)]
+            boolean: Vec<bool>,
+            #[gin(id = 5)]
+            double: Vec<f64>,
+        }
+
+        #[derive(Default, Debug, PartialEq, gin_tonic_derive::Message)]
+        #[gin(root = "crate")]
+        struct Unpacked {
+            #[gin(id = 1, scalar = "int32", packed = false)]
+            int32: Vec<i32>,
+            #[gin(id = 2, scalar = "sint64", packed = false)]
+            sint64: Vec<i64>,
+            #[gin(id = 3, scalar = "fixed32", packed = false)]
+            fixed32: Vec<u32>,
+            #[gin(id = 4, packed = false)]
+            boolean: Vec<bool>,
+            #[gin(id = 5, packed = false)]
+            double: Vec<f64>,
+        }
+
+        fn encode(message: &impl Message) -> Vec<u8> {
+            let size_hint = message.message_size_hint();
+            let mut buffer = vec![0u8; size_hint];
+            let mut encoder = Encoder::new(&mut buffer);
+            message.encode_message(&mut encoder);
+            buffer
+        }
+
+        #[test]
+        fn unpacked_into_packed() {
+            let test = Unpacked {
+                int32: vec![1, 2, -3],
+                sint64: vec![1, 2, -3],
+                fixed32: vec![1, 2, 3],
+                boolean: vec![true, false],
+                double: vec![1.5, -2.5],
+            };
+
+            let buffer = encode(&test);
+            assert_eq!(&buffer[..4], b"");
+
+            let read = Packed::decode_message(&mut Decoder::new(&buffer)).unwrap();
+
+            assert_eq!(test.int32, read.int32);
+            assert_eq!(test.sint64, read.sint64);
+            assert_eq!(test.fixed32, read.fixed32);
+            assert_eq!(test.boolean, read.boolean);
+            assert_eq!(test.double, read.double);
+        }
+
+        #[test]
+        fn packed_into_unpacked() {
+            let test = Packed {
+                int32: vec![1, 2, -3],
+                sint64: vec![1, 2, -3],
+                fixed32: vec![1, 2, 3],
+                boolean: vec![true, false],
+                double: vec![1.5, -2.5],
+            };
+
+            let buffer = encode(&test);
+            let read = Unpacked::decode_message(&mut Decoder::new(&buffer)).unwrap();
+
+            assert_eq!(test.int32, read.int32);
+            assert_eq!(test.sint64, read.sint64);
+            assert_eq!(test.fixed32, read.fixed32);
+            assert_eq!(test.boolean, read.boolean);
+            assert_eq!(test.double, read.double);
+        }
+
+        #[test]
+        fn mixed() {
+            // a packed chunk followed by unpacked elements of the same field
+            let buffer = b"\x0a\x02\x01\x02\x08\x03\x08\x04";
+
+            let read = Packed::decode_message(&mut Decoder::new(buffer)).unwrap();
+            assert_eq!(read.int32, vec![1, 2, 3, 4]);
+
+            let read = Unpacked::decode_message(&mut Decoder::new(buffer)).unwrap();
+            assert_eq!(read.int32, vec![1, 2, 3, 4]);
+        }
+    }
+
+    mod prost_unpacked {
+        use gin_tonic_core::{Message, decoder::Decoder, encoder::Encoder};
+
+        #[derive(Default, Debug, PartialEq, gin_tonic_derive::Message)]
+        #[gin(root = "crate")]
+        struct Test {
+            #[gin(id = 1, scalar = "int32", packed = false)]
+            int32: Vec<i32>,
+            #[gin(id = 2, scalar = "int64", packed = false)]
+            int64: Vec<i64>,
+            #[gin(id = 3, scalar = "uint32", packed = false)]
+            uint32: Vec<u32>,
+            #[gin(id = 4, scalar = "uint64", packed = false)]
+            uint64: Vec<u64>,
+            #[gin(id = 5, scalar = "sint32", packed = false)]
+            sint32: Vec<i32>,
+            #[gin(id = 6, scalar = "sint64", packed = false)]
+            sint64: Vec<i64>,
+            #[gin(id = 7, scalar = "fixed32", packed = false)]
+            fixed32: Vec<u32>,
+            #[gin(id = 8, scalar = "fixed64", packed = false)]
+            fixed64: Vec<u64>,
+            #[gin(id = 9, scalar = "sfixed32", packed = false)]
+            sfixed32: Vec<i32>,
+            #[gin(id = 10, scalar = "sfixed64", packed = false)]
+            sfixed64: Vec<i64>,
+            #[gin(id = 11)]
+            string: Vec<String>,
+            #[gin(id = 12, packed = false)]
+            boolean: Vec<bool>,
+            #[gin(id = 13, packed = false)]
+            float: Vec<f32>,
+            #[gin(id = 14, packed = false)]
+            double: Vec<f64>,
+            #[gin(id = 15)]
+            bytes: Vec<Vec<u8>>,
+        }
+
+        #[derive(Clone, PartialEq, ::prost::Message)]
+        struct Prost {
+            #[prost(int32, repeated, packed = "false", tag = "1")]
+            int32: Vec<i32>,
+            #[prost(int64, repeated, packed = "false", tag = "2")]
+            int64: Vec<i64>,
+            #[prost(uint32, repeated, packed = "false", tag = "3")]
+            uint32: Vec<u32>,
+            #[prost(uint64, repeated, packed = "false", tag = "4")]
+            uint64: Vec<u64>,
+            #[prost(sint32, repeated, packed = "false", tag = "5")]
+            sint32: Vec<i32>,
+            #[prost(sint64, repeated, packed = "false", tag = "6")]
+            sint64: Vec<i64>,
+            #[prost(fixed32, repeated, packed = "false", tag = "7")]
+            fixed32: Vec<u32>,
+            #[prost(fixed64, repeated, packed = "false", tag = "8")]
+            fixed64: Vec<u64>,
+            #[prost(sfixed32, repeated, packed = "false", tag = "9")]
+            sfixed32: Vec<i32>,
+            #[prost(sfixed64, repeated, packed = "false", tag = "10")]
+            sfixed64: Vec<i64>,
+            #[prost(string, repeated, tag = "11")]
+            string: Vec<String>,
+            #[prost(bool, repeated, packed = "false", tag = "12")]
+            boolean: Vec<bool>,
+            #[prost(float, repeated, packed = "false", tag = "13")]
+            float: Vec<f32>,
+            #[prost(double, repeated, packed = "false", tag = "14")]
+            double: Vec<f64>,
+            #[prost(bytes = "vec", repeated, tag = "15")]
+            bytes: Vec<Vec<u8>>,
+        }
+
+        fn test() -> Test {
+            Test {
+                int32: vec![1, -2, i32::MAX],
+                int64: vec![1, -2, i64::MIN],
+                uint32: vec![0, 1, u32::MAX],
+                uint64: vec![0, 1, u64::MAX],
+                sint32: vec![1, -2, i32::MIN],
+                sint64: vec![1, -2, i64::MAX],
+                fixed32: vec![1, 2, u32::MAX],
+                fixed64: vec![1, 2, u64::MAX],
+                sfixed32: vec![1, -2, i32::MIN],
+                sfixed64: vec![1, -2, i64::MIN],
+                string: vec!["hello".into(), String::new()],
+                boolean: vec![true, false],
+                float: vec![1.5, -2.25],
+                double: vec![1.5, -2.25],
+                bytes: vec![vec![1, 2, 3], vec![]],
+            }
+        }
+
+        fn prost() -> Prost {
+            let test = test();
+            Prost {
+                int32: test.int32,
+                int64: test.int64,
+                uint32: test.uint32,
+                uint64: test.uint64,
+                sint32: test.sint32,
+                sint64: test.sint64,
+                fixed32: test.fixed32,
+                fixed64: test.fixed64,
+                sfixed32: test.sfixed32,
+                sfixed64: test.sfixed64,
+                string: test.string,
+                boolean: test.boolean,
+                float: test.float,
+                double: test.double,
+                bytes: test.bytes,
+            }
+        }
+
+        #[test]
+        fn gin_to_prost() {
+            let test = test();
+
+            let size_hint = test.message_size_hint();
+            let mut buffer = vec![0u8; size_hint];
+            let mut encoder = Encoder::new(&mut buffer);
+            test.encode_message(&mut encoder);
+
+            assert_eq!(buffer, ::prost::Message::encode_to_vec(&prost()));
+
+            let read = <Prost as ::prost::Message>::decode(buffer.as_slice()).unwrap();
+            assert_eq!(prost(), read);
+        }
+
+        #[test]
+        fn prost_to_gin() {
+            let buffer = ::prost::Message::encode_to_vec(&prost());
+
+            let mut decoder = Decoder::new(&buffer);
+            let read = Test::decode_message(&mut decoder).unwrap();
+
+            assert_eq!(test(), read);
+        }
+    }
+}
+
+mod bytes {
+    use gin_tonic_core::{Message, decoder::Decoder, encoder::Encoder};
+
+    #[derive(Debug, PartialEq, gin_tonic_derive::Message)]
+    #[gin(root = "crate")]
+    struct Test {
+        #[gin(id = 1, scalar = "bytes")]
+        vec: Vec<u8>,
+        #[gin(id = 2, scalar = "bytes")]
+        repeated: Vec<Vec<u8>>,
+        #[cfg(feature = "bytes")]
+        #[gin(id = 3, scalar = "bytes")]
+        shared: crate::bytes::Bytes,
+        #[gin(id = 4)]
+        array: [u8; 4],
+        #[gin(id = 5)]
+        optional_array: Option<[u8; 2]>,
+    }
+
+    #[test]
+    fn encode_decode() {
+        let test = Test {
+            vec: b"gin".to_vec(),
+            repeated: vec![b"ton".to_vec(), b"ic".to_vec()],
+            #[cfg(feature = "bytes")]
+            shared: crate::bytes::Bytes::from_static(b"shared"),
+            array: [1, 2, 3, 4],
+            optional_array: Some([5, 6]),
+        };
+
+        let size_hint = test.message_size_hint();
+        let mut buffer = vec![0u8; size_hint];
+        let mut encoder = Encoder::new(&mut buffer);
+        test.encode_message(&mut encoder);
+
+        let actual_size = buffer.len();
+        assert!(actual_size > 0);
+        assert_eq!(actual_size, size_hint);
+        assert_eq!(&buffer[..14], b"\x0a\x03gin\x12\x03ton\x12\x02ic");
+
+        let mut decoder = Decoder::new(&buffer);
+        let read = Test::decode_message(&mut decoder).unwrap();
+
+        assert_eq!(test, read)
+    }
+
+    #[test]
+    fn decode_array_length_mismatch() {
+        let buffer = b"\x0a\x00\x22\x03\x01\x02\x03";
+
+        let mut decoder = Decoder::new(buffer);
+        assert!(matches!(
+            Test::decode_message(&mut decoder),
+            Err(crate::ProtoError::Field { path, source })
+                if path == "Test.array" && matches!(*source, crate::ProtoError::ArrayFromSlice(_))
+        ));
+    }
 }
 
 mod nested {
@@ -503,6 +797,98 @@ mod map {
             assert_eq!(test, read)
         }
     }
+
+    mod containers {
+        use std::collections::{BTreeMap, HashMap};
+
+        use gin_tonic_core::{Message, decoder::Decoder, encoder::Encoder};
+
+        #[derive(Default, Debug, PartialEq, gin_tonic_derive::Message)]
+        #[gin(root = "crate")]
+        struct Test {
+            #[gin(id = 1, key_scalar = "uint32")]
+            btree: BTreeMap<u32, String>,
+            #[gin(id = 2, value_scalar = "uint32")]
+            hash: HashMap<String, u32>,
+            #[gin(id = 3, value_scalar = "uint32")]
+            hash_with_hasher: HashMap<String, u32, std::hash::RandomState>,
+        }
+
+        #[test]
+        fn encode_decode() {
+            let mut test = Test::default();
+            test.btree.insert(20, "twenty".into());
+            test.btree.insert(10, "ten".into());
+            test.hash.insert("ten".into(), 10);
+            test.hash_with_hasher.insert("twenty".into(), 20);
+
+            let size_hint = test.message_size_hint();
+            let mut buffer = vec![0u8; size_hint];
+            let mut encoder = Encoder::new(&mut buffer);
+            test.encode_message(&mut encoder);
+
+            let actual_size = buffer.len();
+            assert!(actual_size > 0);
+            assert_eq!(actual_size, size_hint);
+            // btree maps encode in key order
+            assert_eq!(
+                &buffer[..21],
+                b"\x0a\x07\x08\x0a\x12\x03ten\x0a\x0a\x08\x14\x12\x06twenty"
+            );
+
+            let mut decoder = Decoder::new(&buffer);
+            let read = Test::decode_message(&mut decoder).unwrap();
+
+            assert_eq!(test, read)
+        }
+    }
+
+    mod deterministic {
+        use std::collections::HashMap;
+
+        use gin_tonic_core::{Message, decoder::Decoder};
+
+        #[derive(Default, Debug, PartialEq, gin_tonic_derive::Message)]
+        #[gin(root = "crate")]
+        struct Inner {
+            #[gin(id = 1, key_scalar = "uint32")]
+            map: HashMap<u32, String>,
+        }
+
+        #[derive(Default, Debug, PartialEq, gin_tonic_derive::Message)]
+        #[gin(root = "crate")]
+        struct Test {
+            #[gin(id = 1, value_scalar = "uint32")]
+            map: HashMap<String, u32>,
+            #[gin(id = 2)]
+            inner: Inner,
+        }
+
+        fn build(keys: impl Iterator<Item = u32>) -> Test {
+            let mut test = Test::default();
+            for key in keys {
+                test.map.insert(key.to_string(), key);
+                test.inner.map.insert(key, key.to_string());
+            }
+            test
+        }
+
+        #[test]
+        fn same_bytes_across_runs() {
+            let expected = build(0..32).encode_deterministic();
+            assert_eq!(expected.len(), build(0..32).message_size_hint());
+
+            // each map gets a new random hasher state, nested maps are sorted too
+            for _ in 0..16 {
+                assert_eq!(expected, build((0..32).rev()).encode_deterministic());
+            }
+
+            let mut decoder = Decoder::new(&expected);
+            let read = Test::decode_message(&mut decoder).unwrap();
+
+            assert_eq!(build(0..32), read)
+        }
+    }
 }
 
 mod enumeration {
@@ -683,6 +1069,102 @@ mod enumeration {
             assert_eq!(test, read)
         }
     }
+
+    mod string_repr {
+        use gin_tonic_core::{Message, decoder::Decoder, encoder::Encoder};
+
+        #[derive(Debug, PartialEq, gin_tonic_derive::Enumeration)]
+        #[gin(root = "crate", repr = "string")]
+        enum Color {
+            #[gin(name = "COLOR_RED")]
+            Red,
+            Green,
+        }
+
+        #[derive(Debug, PartialEq, gin_tonic_derive::Message)]
+        #[gin(root = "crate")]
+        struct Test {
+            #[gin(id = 1, scalar = "string")]
+            color: Color,
+            #[gin(id = 2, scalar = "string")]
+            colors: Vec<Color>,
+        }
+
+        #[test]
+        fn names() {
+            assert_eq!(Color::Red.as_str_name(), "COLOR_RED");
+            assert_eq!(Color::Green.as_str_name(), "Green");
+            assert_eq!(Color::from_str_name("COLOR_RED"), Some(Color::Red));
+            assert_eq!(Color::from_str_name("Red"), None);
+
+            // int32 enumerations get the same pair
+            assert_eq!(super::TestEnum::A.as_str_name(), "A");
+            assert_eq!(
+                super::TestEnum::from_str_name("B"),
+                Some(super::TestEnum::B)
+            );
+        }
+
+        #[test]
+        fn encode_decode() {
+            let test = Test {
+                color: Color::Red,
+                colors: vec![Color::Green],
+            };
+
+            let size_hint = test.message_size_hint();
+            let mut buffer = vec![0u8; size_hint];
+            let mut encoder = Encoder::new(&mut buffer);
+            test.encode_message(&mut encoder);
+
+            assert_eq!(size_hint, buffer.len());
+            assert_eq!(buffer, b"\x0a\x09COLOR_RED\x12\x05Green");
+
+            let mut decoder = Decoder::new(&buffer);
+            let read = Test::decode_message(&mut decoder).unwrap();
+
+            assert_eq!(test, read)
+        }
+
+        #[derive(Debug, PartialEq, gin_tonic_derive::Enumeration)]
+        #[gin(root = "crate")]
+        enum Generated {
+            #[gin(id = 1, name = "GENERATED_FOO_BAR")]
+            FooBar,
+            #[gin(id = 2, name = "GENERATED_BAZ")]
+            Baz,
+        }
+
+        #[test]
+        fn reflection() {
+            assert_eq!(Generated::FooBar.to_string(), "GENERATED_FOO_BAR");
+            assert_eq!(
+                "GENERATED_BAZ".parse::<Generated>().unwrap(),
+                Generated::Baz
+            );
+            assert!(matches!(
+                "Baz".parse::<Generated>(),
+                Err(crate::ProtoError::UnknownEnumName(_))
+            ));
+            assert_eq!(
+                Generated::all_variants().collect::<Vec<_>>(),
+                vec![Generated::FooBar, Generated::Baz]
+            );
+            assert_eq!(Color::all_variants().count(), 2);
+        }
+
+        #[test]
+        fn decode_unknown_name() {
+            let mut decoder = Decoder::new(b"\x0a\x04Blue");
+
+            assert!(matches!(
+                Test::decode_message(&mut decoder),
+                Err(crate::ProtoError::Field { path, source })
+                    if path == "Test.color"
+                        && matches!(&*source, crate::ProtoError::UnknownEnumName(name) if name == "Blue")
+            ));
+        }
+    }
 }
 
 mod one_of {
@@ -846,3 +1328,2080 @@ mod one_of {
         }
     }
 }
+
+mod tuple_struct {
+    use gin_tonic_core::{Message, decoder::Decoder, encoder::Encoder};
+
+    #[derive(Debug, PartialEq, gin_tonic_derive::Message)]
+    #[gin(root = "crate")]
+    struct Pair(
+        #[gin(id = 1, scalar = "uint32")] u32,
+        #[gin(id = 2)] Option<String>,
+    );
+
+    #[derive(Debug, PartialEq, gin_tonic_derive::Message)]
+    #[gin(root = "crate", transparent)]
+    struct Count(u32);
+
+    #[derive(Debug, PartialEq, gin_tonic_derive::Message)]
+    #[gin(root = "crate", transparent)]
+    struct Name {
+        value: String,
+    }
+
+    #[derive(Debug, PartialEq, gin_tonic_derive::Message)]
+    #[gin(root = "crate")]
+    struct Test {
+        #[gin(id = 1, scalar = "uint32")]
+        count: Count,
+        #[gin(id = 2, scalar = "uint32")]
+        counts: Vec<Count>,
+        #[gin(id = 3, scalar = "string")]
+        name: Option<Name>,
+        #[gin(id = 4)]
+        pair: Pair,
+    }
+
+    fn encode(message: &impl Message) -> Vec<u8> {
+        let size_hint = message.message_size_hint();
+        let mut buffer = vec![0u8; size_hint];
+        let mut encoder = Encoder::new(&mut buffer);
+        message.encode_message(&mut encoder);
+        assert_eq!(size_hint, buffer.len());
+        buffer
+    }
+
+    #[test]
+    fn encode_decode_tuple() {
+        let test = Pair(150, Some("gin".into()));
+
+        let buffer = encode(&test);
+        assert_eq!(buffer, b"\x08\x96\x01\x12\x03gin");
+
+        let mut decoder = Decoder::new(&buffer);
+        let read = Pair::decode_message(&mut decoder).unwrap();
+
+        assert_eq!(test, read)
+    }
+
+    #[test]
+    fn encode_decode_transparent() {
+        let test = Test {
+            count: Count(1),
+            counts: vec![Count(2), Count(3)],
+            name: Some(Name {
+                value: "tonic".into(),
+            }),
+            pair: Pair(4, None),
+        };
+
+        // newtypes are encoded exactly like the wrapped field
+        let buffer = encode(&test);
+        assert_eq!(
+            buffer,
+            b"\x08\x01\x12\x02\x02\x03\x1a\x05tonic\x22\x02\x08\x04"
+        );
+
+        let mut decoder = Decoder::new(&buffer);
+        let read = Test::decode_message(&mut decoder).unwrap();
+
+        assert_eq!(test, read)
+    }
+}
+
+mod generics {
+    use std::collections::BTreeMap;
+
+    use gin_tonic_core::{Message, decoder::Decoder, encoder::Encoder};
+
+    #[derive(Debug, PartialEq, gin_tonic_derive::Message)]
+    #[gin(root = "crate")]
+    struct Item {
+        #[gin(id = 1)]
+        name: String,
+    }
+
+    #[derive(Debug, PartialEq, gin_tonic_derive::Message)]
+    #[gin(root = "crate")]
+    struct Page<T>
+    where
+        T: std::fmt::Debug,
+    {
+        #[gin(id = 1)]
+        items: Vec<T>,
+        #[gin(id = 2)]
+        first: Option<T>,
+        #[gin(id = 3, scalar = "uint32")]
+        total: u32,
+    }
+
+    #[derive(Debug, PartialEq, gin_tonic_derive::Message)]
+    #[gin(root = "crate")]
+    struct Numbers<N, V> {
+        #[gin(id = 1, scalar = "sint32")]
+        numbers: Vec<N>,
+        #[gin(id = 2, scalar = "sint32")]
+        last: N,
+        #[gin(id = 3, value_scalar = "string")]
+        labels: BTreeMap<String, V>,
+    }
+
+    #[derive(Debug, PartialEq, gin_tonic_derive::Message)]
+    #[gin(root = "crate", transparent)]
+    struct Id<T>(T);
+
+    fn round_trip<T: Message + PartialEq + std::fmt::Debug>(message: T) {
+        let size_hint = message.message_size_hint();
+        let mut buffer = vec![0u8; size_hint];
+        let mut encoder = Encoder::new(&mut buffer);
+        message.encode_message(&mut encoder);
+        assert_eq!(size_hint, buffer.len());
+
+        let mut decoder = Decoder::new(&buffer);
+        let read = T::decode_message(&mut decoder).unwrap();
+
+        assert_eq!(message, read)
+    }
+
+    #[test]
+    fn message_type_param() {
+        round_trip(Page {
+            items: vec![Item { name: "a".into() }, Item { name: "b".into() }],
+            first: Some(Item { name: "a".into() }),
+            total: 2,
+        });
+    }
+
+    #[test]
+    fn scalar_type_params() {
+        let mut labels = BTreeMap::new();
+        labels.insert(String::from("x"), String::from("y"));
+
+        round_trip(Numbers {
+            numbers: vec![Id(-1i32), Id(2)],
+            last: Id(3),
+            labels,
+        });
+    }
+}
+
+mod defaults {
+    use gin_tonic_core::{Message, ProtoError, decoder::Decoder};
+
+    #[derive(Debug, PartialEq, gin_tonic_derive::Enumeration)]
+    #[gin(root = "crate")]
+    enum Mode {
+        #[gin(id = 1)]
+        Fast,
+        #[gin(id = 2)]
+        Slow,
+    }
+
+    #[derive(Debug, PartialEq, gin_tonic_derive::Message)]
+    #[gin(root = "crate")]
+    struct Legacy {
+        #[gin(id = 1, scalar = "int32")]
+        id: i32,
+        #[gin(id = 2, scalar = "int64", default = "-7i64")]
+        count: i64,
+        #[gin(id = 3, default = "String::from(\"none\")")]
+        label: String,
+        #[gin(id = 4, scalar = "int32", default = "Mode::Slow")]
+        mode: Mode,
+    }
+
+    #[test]
+    fn absent_fields_use_default() {
+        // id = 5, mode = Fast
+        let buffer = b"\x08\x05\x20\x01";
+        let mut decoder = Decoder::new(buffer);
+        let read = Legacy::decode_message(&mut decoder).unwrap();
+
+        assert_eq!(
+            read,
+            Legacy {
+                id: 5,
+                count: -7,
+                label: String::from("none"),
+                mode: Mode::Fast,
+            }
+        );
+    }
+
+    #[test]
+    fn required_without_default() {
+        let buffer = b"\x10\x01";
+        let mut decoder = Decoder::new(buffer);
+        let err = Legacy::decode_message(&mut decoder).unwrap_err();
+
+        assert!(matches!(err, ProtoError::MissingField(1)));
+    }
+}
+
+mod extensions {
+    use gin_tonic_core::{
+        Extension, ExtensionSet, Message,
+        decoder::Decoder,
+        encoder::Encoder,
+        scalars::{ProtoString, UInt32},
+    };
+
+    const NOTE: Extension<ProtoString, String> = Extension::new(100);
+    const PRIORITY: Extension<UInt32, u32> = Extension::new(101);
+
+    #[derive(Debug, PartialEq, gin_tonic_derive::Message)]
+    #[gin(root = "crate")]
+    struct Extendable {
+        #[gin(id = 1)]
+        name: String,
+        #[gin(extensions)]
+        extensions: ExtensionSet,
+    }
+
+    #[derive(Debug, PartialEq, gin_tonic_derive::Message)]
+    #[gin(root = "crate")]
+    struct Plain {
+        #[gin(id = 1)]
+        name: String,
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut test = Extendable {
+            name: String::from("gin"),
+            extensions: ExtensionSet::default(),
+        };
+        test.extensions.set(&NOTE, &String::from("tonic")).unwrap();
+        test.extensions.set(&PRIORITY, &3).unwrap();
+
+        let size_hint = test.message_size_hint();
+        let mut buffer = vec![0u8; size_hint];
+        let mut encoder = Encoder::new(&mut buffer);
+        test.encode_message(&mut encoder);
+        assert_eq!(size_hint, buffer.len());
+
+        let mut decoder = Decoder::new(&buffer);
+        let read = Extendable::decode_message(&mut decoder).unwrap();
+
+        assert_eq!(
+            read.extensions.get(&NOTE).unwrap(),
+            Some(String::from("tonic"))
+        );
+        assert_eq!(read.extensions.get(&PRIORITY).unwrap(), Some(3));
+        assert_eq!(test, read);
+
+        // messages without extension storage skip the unknown fields
+        let mut decoder = Decoder::new(&buffer);
+        let plain = Plain::decode_message(&mut decoder).unwrap();
+        assert_eq!(plain.name, "gin");
+    }
+}
+
+mod groups {
+    use gin_tonic_core::{
+        Message,
+        decoder::{DecodeOptions, Decoder},
+        encoder::Encoder,
+    };
+
+    use crate::ProtoError;
+
+    #[derive(Debug, PartialEq, gin_tonic_derive::Message)]
+    #[gin(root = "crate")]
+    struct Header {
+        #[gin(id = 2, scalar = "int32")]
+        version: i32,
+    }
+
+    #[derive(Debug, PartialEq, gin_tonic_derive::Message)]
+    #[gin(root = "crate")]
+    struct Entry {
+        #[gin(id = 4)]
+        key: Option<String>,
+    }
+
+    #[derive(Debug, PartialEq, gin_tonic_derive::Message)]
+    #[gin(root = "crate")]
+    struct Test {
+        #[gin(id = 1, group)]
+        header: Header,
+        #[gin(id = 3, group)]
+        entries: Vec<Entry>,
+        #[gin(id = 5, group)]
+        trailer: Option<Header>,
+        #[gin(id = 6, scalar = "int32")]
+        tail: i32,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    struct ProstHeader {
+        #[prost(int32, tag = "2")]
+        version: i32,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    struct ProstEntry {
+        #[prost(string, optional, tag = "4")]
+        key: Option<String>,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    struct Prost {
+        #[prost(group, optional, tag = "1")]
+        header: Option<ProstHeader>,
+        #[prost(group, repeated, tag = "3")]
+        entries: Vec<ProstEntry>,
+        #[prost(group, optional, tag = "5")]
+        trailer: Option<ProstHeader>,
+        #[prost(int32, tag = "6")]
+        tail: i32,
+    }
+
+    #[derive(Debug, PartialEq, gin_tonic_derive::Message)]
+    #[gin(root = "crate")]
+    struct Skipping {
+        #[gin(id = 6, scalar = "int32")]
+        tail: i32,
+    }
+
+    fn test() -> Test {
+        Test {
+            header: Header { version: 1 },
+            entries: vec![
+                Entry {
+                    key: Some("a".into()),
+                },
+                Entry { key: None },
+            ],
+            trailer: None,
+            tail: 7,
+        }
+    }
+
+    fn prost() -> Prost {
+        Prost {
+            header: Some(ProstHeader { version: 1 }),
+            entries: vec![
+                ProstEntry {
+                    key: Some("a".into()),
+                },
+                ProstEntry { key: None },
+            ],
+            trailer: None,
+            tail: 7,
+        }
+    }
+
+    #[test]
+    fn gin_to_prost() {
+        let test = test();
+
+        let size_hint = test.message_size_hint();
+        let mut buffer = vec![0u8; size_hint];
+        let mut encoder = Encoder::new(&mut buffer);
+        test.encode_message(&mut encoder);
+
+        assert_eq!(buffer, ::prost::Message::encode_to_vec(&prost()));
+    }
+
+    #[test]
+    fn prost_to_gin() {
+        let buffer = ::prost::Message::encode_to_vec(&prost());
+
+        let mut decoder = Decoder::new(&buffer);
+        let read = Test::decode_message(&mut decoder).unwrap();
+        assert_eq!(test(), read);
+
+        // unknown groups are skipped including nested fields
+        let mut decoder = Decoder::new(&buffer);
+        let read = Skipping::decode_message(&mut decoder).unwrap();
+        assert_eq!(read.tail, 7);
+    }
+
+    #[derive(Debug, PartialEq, gin_tonic_derive::Message)]
+    #[gin(root = "crate")]
+    struct Outer {
+        #[gin(id = 1, group)]
+        inner: Option<Inner>,
+    }
+
+    #[derive(Debug, PartialEq, gin_tonic_derive::Message)]
+    #[gin(root = "crate")]
+    struct Inner {
+        #[gin(id = 1, group)]
+        header: Option<Header>,
+    }
+
+    #[test]
+    fn nesting_limit() {
+        // group 1 { group 1 { 2: 3 } }
+        let buffer = b"\x0b\x0b\x10\x03\x0c\x0c";
+        let read = Outer::decode_message(&mut Decoder::new(buffer)).unwrap();
+        assert_eq!(read.inner.unwrap().header, Some(Header { version: 3 }));
+
+        let options = DecodeOptions::new().max_depth(1);
+        let err = Outer::decode_message(&mut Decoder::with_options(buffer, options).unwrap())
+            .unwrap_err();
+        assert_eq!(err.path(), Some("Outer.inner.header"));
+        assert!(matches!(
+            err,
+            ProtoError::Field { source, .. } if matches!(*source, ProtoError::RecursionLimitExceeded(1))
+        ));
+
+        // unknown groups nested deep enough to overflow the stack without a limit
+        let mut buffer = vec![0x3b; 200_000];
+        buffer.extend(vec![0x3c; 200_000]);
+        let err = Skipping::decode_message(&mut Decoder::new(&buffer)).unwrap_err();
+        assert_eq!(err.path(), Some("Skipping.7"));
+        assert!(matches!(
+            err,
+            ProtoError::Field { source, .. } if matches!(*source, ProtoError::RecursionLimitExceeded(100))
+        ));
+    }
+
+    #[test]
+    fn unterminated() {
+        // the end group tag of the outer group is missing
+        let err = Outer::decode_message(&mut Decoder::new(b"\x0b\x0b\x10\x03\x0c")).unwrap_err();
+        assert_eq!(err.path(), Some("Outer.inner"));
+        assert!(matches!(
+            err,
+            ProtoError::Field { source, .. } if matches!(*source, ProtoError::UnexpectedEof)
+        ));
+
+        // an end group tag not matching the group
+        let err =
+            Outer::decode_message(&mut Decoder::new(b"\x0b\x0b\x10\x03\x1c\x0c\x0c")).unwrap_err();
+        assert_eq!(err.path(), Some("Outer.inner.header.3"));
+        assert!(matches!(
+            err,
+            ProtoError::Field { source, .. } if matches!(*source, ProtoError::UnexpectedEndGroup(3))
+        ));
+    }
+}
+
+mod merge {
+    use gin_tonic_core::{Message, decoder::Decoder, encoder::Encoder};
+
+    #[derive(Clone, Debug, PartialEq, gin_tonic_derive::Message)]
+    #[gin(root = "crate")]
+    struct Inner {
+        #[gin(id = 1, scalar = "int32")]
+        a: Option<i32>,
+        #[gin(id = 2, scalar = "int32")]
+        b: Option<i32>,
+    }
+
+    #[derive(Clone, Debug, PartialEq, gin_tonic_derive::Message)]
+    #[gin(root = "crate")]
+    struct Outer {
+        #[gin(id = 1)]
+        name: Option<String>,
+        #[gin(id = 2)]
+        inner: Inner,
+        #[gin(id = 3)]
+        optional_inner: Option<Inner>,
+        #[gin(id = 4, scalar = "uint32")]
+        values: Vec<u32>,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    struct ProstInner {
+        #[prost(int32, optional, tag = "1")]
+        a: Option<i32>,
+        #[prost(int32, optional, tag = "2")]
+        b: Option<i32>,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    struct ProstOuter {
+        #[prost(string, optional, tag = "1")]
+        name: Option<String>,
+        #[prost(message, optional, tag = "2")]
+        inner: Option<ProstInner>,
+        #[prost(message, optional, tag = "3")]
+        optional_inner: Option<ProstInner>,
+        #[prost(uint32, repeated, tag = "4")]
+        values: Vec<u32>,
+    }
+
+    fn encode(message: &impl Message) -> Vec<u8> {
+        let mut buffer = vec![0u8; message.message_size_hint()];
+        let mut encoder = Encoder::new(&mut buffer);
+        message.encode_message(&mut encoder);
+        buffer
+    }
+
+    fn first() -> Outer {
+        Outer {
+            name: Some("first".into()),
+            inner: Inner {
+                a: Some(1),
+                b: None,
+            },
+            optional_inner: Some(Inner {
+                a: None,
+                b: Some(2),
+            }),
+            values: vec![1, 2],
+        }
+    }
+
+    fn second() -> Outer {
+        Outer {
+            name: Some("second".into()),
+            inner: Inner {
+                a: None,
+                b: Some(3),
+            },
+            optional_inner: Some(Inner {
+                a: Some(4),
+                b: Some(5),
+            }),
+            values: vec![3],
+        }
+    }
+
+    fn merged() -> Outer {
+        Outer {
+            name: Some("second".into()),
+            inner: Inner {
+                a: Some(1),
+                b: Some(3),
+            },
+            optional_inner: Some(Inner {
+                a: Some(4),
+                b: Some(5),
+            }),
+            values: vec![1, 2, 3],
+        }
+    }
+
+    #[test]
+    fn concatenated() {
+        let mut buffer = encode(&first());
+        buffer.extend(encode(&second()));
+
+        let mut decoder = Decoder::new(&buffer);
+        let read = Outer::decode_message(&mut decoder).unwrap();
+        assert_eq!(read, merged());
+
+        // prost merges the same way
+        let prost = <ProstOuter as ::prost::Message>::decode(buffer.as_slice()).unwrap();
+        assert_eq!(encode(&read), ::prost::Message::encode_to_vec(&prost));
+    }
+
+    #[test]
+    fn merge_message() {
+        let mut message = first();
+        let buffer = encode(&second());
+
+        let mut decoder = Decoder::new(&buffer);
+        message.merge_message(&mut decoder).unwrap();
+        assert_eq!(message, merged());
+    }
+
+    #[derive(Clone, Debug, PartialEq, gin_tonic_derive::OneOf)]
+    #[gin(root = "crate")]
+    enum Choice {
+        #[gin(id = 7)]
+        Inner(Inner),
+        #[gin(id = 8, scalar = "uint32")]
+        Number(u32),
+    }
+
+    #[derive(Clone, Debug, PartialEq, gin_tonic_derive::Message)]
+    #[gin(root = "crate")]
+    struct Nested {
+        #[gin(id = 5, group)]
+        group: Inner,
+        #[gin(id = 6, group)]
+        optional_group: Option<Inner>,
+        #[gin(id = 0, oneof)]
+        choice: Choice,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    enum ProstChoice {
+        #[prost(message, tag = "7")]
+        Inner(ProstInner),
+        #[prost(uint32, tag = "8")]
+        Number(u32),
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    struct ProstNested {
+        #[prost(group, optional, tag = "5")]
+        group: Option<ProstInner>,
+        #[prost(group, optional, tag = "6")]
+        optional_group: Option<ProstInner>,
+        #[prost(oneof = "ProstChoice", tags = "7, 8")]
+        choice: Option<ProstChoice>,
+    }
+
+    fn inner(a: Option<i32>, b: Option<i32>) -> Inner {
+        Inner { a, b }
+    }
+
+    fn nested(first: bool) -> Nested {
+        if first {
+            Nested {
+                group: inner(Some(1), None),
+                optional_group: Some(inner(None, Some(2))),
+                choice: Choice::Inner(inner(Some(1), None)),
+            }
+        } else {
+            Nested {
+                group: inner(None, Some(3)),
+                optional_group: Some(inner(Some(4), None)),
+                choice: Choice::Inner(inner(None, Some(5))),
+            }
+        }
+    }
+
+    fn nested_merged() -> Nested {
+        Nested {
+            group: inner(Some(1), Some(3)),
+            optional_group: Some(inner(Some(4), Some(2))),
+            choice: Choice::Inner(inner(Some(1), Some(5))),
+        }
+    }
+
+    #[test]
+    fn concatenated_groups_and_oneof() {
+        let mut buffer = encode(&nested(true));
+        buffer.extend(encode(&nested(false)));
+
+        let mut decoder = Decoder::new(&buffer);
+        let read = Nested::decode_message(&mut decoder).unwrap();
+        assert_eq!(read, nested_merged());
+
+        // prost merges the same way
+        let prost = <ProstNested as ::prost::Message>::decode(buffer.as_slice()).unwrap();
+        assert_eq!(encode(&read), ::prost::Message::encode_to_vec(&prost));
+
+        // the oneof on its own merges as well
+        let mut buffer = encode(&Choice::Inner(inner(Some(1), None)));
+        buffer.extend(encode(&Choice::Inner(inner(None, Some(5)))));
+        let read = Choice::decode_message(&mut Decoder::new(&buffer)).unwrap();
+        assert_eq!(read, Choice::Inner(inner(Some(1), Some(5))));
+    }
+
+    #[test]
+    fn merge_groups_and_oneof() {
+        let mut message = nested(true);
+        let buffer = encode(&nested(false));
+
+        let mut decoder = Decoder::new(&buffer);
+        message.merge_message(&mut decoder).unwrap();
+        assert_eq!(message, nested_merged());
+
+        // another variant replaces the value
+        let buffer = encode(&Nested {
+            choice: Choice::Number(9),
+            ..nested(false)
+        });
+        message.merge_message(&mut Decoder::new(&buffer)).unwrap();
+        assert_eq!(message.choice, Choice::Number(9));
+    }
+}
+
+mod field_path {
+    use gin_tonic_core::{Message, decoder::Decoder};
+
+    use crate::ProtoError;
+
+    #[derive(Debug, PartialEq, gin_tonic_derive::Message)]
+    #[gin(root = "crate")]
+    struct Echo {
+        #[gin(id = 1)]
+        ip: String,
+    }
+
+    #[derive(Debug, PartialEq, gin_tonic_derive::OneOf)]
+    #[gin(root = "crate")]
+    enum Choice {
+        #[gin(id = 2, scalar = "uint32")]
+        Number(u32),
+        #[gin(id = 3)]
+        Text(String),
+    }
+
+    #[derive(Debug, PartialEq, gin_tonic_derive::Message)]
+    #[gin(root = "crate")]
+    struct EchoResponse {
+        #[gin(id = 1)]
+        echo: Echo,
+        #[gin(id = 0, oneof)]
+        choice: Choice,
+    }
+
+    #[derive(Debug, PartialEq, gin_tonic_derive::Message)]
+    #[gin(root = "crate")]
+    struct Tuple(#[gin(id = 1)] Echo);
+
+    fn decode<T: Message + std::fmt::Debug>(buffer: &[u8]) -> ProtoError {
+        T::decode_message(&mut Decoder::new(buffer)).unwrap_err()
+    }
+
+    #[test]
+    fn nested() {
+        let err = decode::<EchoResponse>(b"\x0a\x03\x0a\x01\xff");
+        assert_eq!(err.path(), Some("EchoResponse.echo.ip"));
+        assert!(matches!(
+            err,
+            ProtoError::Field { source, .. } if matches!(*source, ProtoError::Utf8(_))
+        ));
+        assert!(
+            decode::<EchoResponse>(b"\x0a\x03\x0a\x01\xff")
+                .to_string()
+                .starts_with("EchoResponse.echo.ip: ")
+        );
+
+        // missing fields are reported by the nested message
+        let err = decode::<EchoResponse>(b"\x0a\x00");
+        assert_eq!(err.path(), Some("EchoResponse.echo"));
+        assert!(matches!(
+            err,
+            ProtoError::Field { source, .. } if matches!(*source, ProtoError::MissingField(1))
+        ));
+    }
+
+    #[test]
+    fn oneof_and_unknown() {
+        let err = decode::<EchoResponse>(b"\x1a\x01\xff");
+        assert_eq!(err.path(), Some("EchoResponse.choice"));
+
+        let err = decode::<EchoResponse>(b"\x4f");
+        assert_eq!(err.path(), Some("EchoResponse.9"));
+        assert!(matches!(
+            err,
+            ProtoError::Field { source, .. } if matches!(*source, ProtoError::UnknownWireType(7))
+        ));
+    }
+
+    #[test]
+    fn tuple() {
+        let err = decode::<Tuple>(b"\x0a\x03\x0a\x01\xff");
+        assert_eq!(err.path(), Some("Tuple.0.ip"));
+    }
+
+    #[test]
+    fn merge() {
+        let mut message = EchoResponse {
+            echo: Echo {
+                ip: String::from("127.0.0.1"),
+            },
+            choice: Choice::Number(1),
+        };
+        let err = message
+            .merge_message(&mut Decoder::new(b"\x0a\x03\x0a\x01\xff"))
+            .unwrap_err();
+        assert_eq!(err.path(), Some("EchoResponse.echo.ip"));
+    }
+}
+
+#[cfg(feature = "tonic")]
+mod status_mapping {
+    use gin_tonic_core::{
+        Message,
+        decoder::{DecodeOptions, Decoder},
+    };
+    use tonic::codec::Codec;
+
+    use crate::{DefaultStatusMapping, GinCodec, ProtoError, StatusMapping};
+
+    #[derive(Debug, gin_tonic_derive::Message)]
+    #[gin(root = "crate")]
+    struct Request {
+        #[gin(id = 1)]
+        name: String,
+    }
+
+    #[test]
+    fn default_mapping() {
+        let err = Request::decode_message(&mut Decoder::new(b"\x0a\x01\xff")).unwrap_err();
+        let status = DefaultStatusMapping::decode_error(err);
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+        assert!(status.message().starts_with("Request.name: "));
+
+        let status =
+            DefaultStatusMapping::decode_error(ProtoError::Io(std::io::Error::other("io")));
+        assert_eq!(status.code(), tonic::Code::Internal);
+    }
+
+    #[derive(Debug, gin_tonic_derive::Message)]
+    #[gin(root = "crate")]
+    struct Names {
+        #[gin(id = 1)]
+        names: Vec<String>,
+    }
+
+    struct Unavailable;
+
+    impl StatusMapping for Unavailable {
+        fn decode_error(err: ProtoError) -> tonic::Status {
+            tonic::Status::unavailable(err.to_string())
+        }
+    }
+
+    async fn decode<M: StatusMapping + Send + 'static>(
+        mut codec: GinCodec<Names, Names, M>,
+    ) -> Result<Option<Names>, tonic::Status> {
+        // names: ["a", "b", "c"] in an uncompressed grpc frame
+        let body = String::from("\0\0\0\0\x09\x0a\x01a\x0a\x01b\x0a\x01c");
+        let mut stream = tonic::Streaming::new_request(codec.decoder(), body, None, None);
+        stream.message().await
+    }
+
+    #[tokio::test]
+    async fn codec_options() {
+        let names = decode(GinCodec::<_, _, DefaultStatusMapping>::default())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(names.names, vec!["a", "b", "c"]);
+
+        let codec = GinCodec::<_, _, DefaultStatusMapping>::default()
+            .with_decode_options(DecodeOptions::new().max_repeated(2));
+        let status = decode(codec).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::ResourceExhausted);
+
+        let codec = GinCodec::<_, _, Unavailable>::default()
+            .with_decode_options(DecodeOptions::new().max_repeated(2));
+        let status = decode(codec).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::Unavailable);
+
+        let codec = GinCodec::<_, _, Unavailable>::default()
+            .with_decode_options(DecodeOptions::new().max_repeated(3));
+        assert!(decode(codec).await.unwrap().is_some());
+    }
+}
+
+mod limits {
+    use gin_tonic_core::{
+        Message,
+        decoder::{DecodeOptions, Decoder},
+    };
+    use std::collections::HashMap;
+
+    use crate::ProtoError;
+
+    #[derive(Debug, PartialEq, gin_tonic_derive::Message)]
+    #[gin(root = "crate")]
+    struct Inner {
+        #[gin(id = 1)]
+        names: Vec<String>,
+        #[gin(id = 2, key_scalar = "uint32", value_scalar = "uint32")]
+        counts: HashMap<u32, u32>,
+    }
+
+    #[derive(Debug, PartialEq, gin_tonic_derive::Message)]
+    #[gin(root = "crate")]
+    struct Outer {
+        #[gin(id = 1)]
+        inner: Inner,
+    }
+
+    fn decode(buffer: &[u8], options: DecodeOptions) -> Result<Outer, ProtoError> {
+        Outer::decode_message(&mut Decoder::with_options(buffer, options)?)
+    }
+
+    #[test]
+    fn repeated() {
+        // inner { names: ["a", "b", "c"] }
+        let buffer = b"\x0a\x09\x0a\x01a\x0a\x01b\x0a\x01c";
+        assert!(decode(buffer, DecodeOptions::new()).is_ok());
+
+        let err = decode(buffer, DecodeOptions::new().max_repeated(2)).unwrap_err();
+        assert_eq!(err.path(), Some("Outer.inner.names"));
+        assert!(matches!(
+            err,
+            ProtoError::Field { source, .. } if matches!(*source, ProtoError::TooManyElements(2))
+        ));
+
+        let err = decode(buffer, DecodeOptions::new().max_message_size(8)).unwrap_err();
+        assert!(matches!(err, ProtoError::MessageTooLarge(8)));
+    }
+
+    #[test]
+    fn map() {
+        // inner { counts: { 1: 1, 2: 2 } }
+        let buffer = b"\x0a\x0c\x12\x04\x08\x01\x10\x01\x12\x04\x08\x02\x10\x02";
+        assert_eq!(
+            decode(buffer, DecodeOptions::new())
+                .unwrap()
+                .inner
+                .counts
+                .len(),
+            2
+        );
+
+        let err = decode(buffer, DecodeOptions::new().max_repeated(1)).unwrap_err();
+        assert_eq!(err.path(), Some("Outer.inner.counts"));
+    }
+
+    #[test]
+    fn depth() {
+        // inner { counts: { 1: 1 } }, map entries are not counted as nesting
+        let buffer = b"\x0a\x06\x12\x04\x08\x01\x10\x01";
+        assert!(decode(buffer, DecodeOptions::new().max_depth(1)).is_ok());
+
+        let err = decode(buffer, DecodeOptions::new().max_depth(0)).unwrap_err();
+        assert_eq!(err.path(), Some("Outer.inner"));
+        assert!(matches!(
+            err,
+            ProtoError::Field { source, .. } if matches!(*source, ProtoError::RecursionLimitExceeded(0))
+        ));
+    }
+}
+
+mod utf8 {
+    use std::collections::BTreeMap;
+
+    use gin_tonic_core::{Message, decoder::Decoder};
+
+    use crate::ProtoError;
+
+    #[derive(Debug, PartialEq, gin_tonic_derive::OneOf)]
+    #[gin(root = "crate")]
+    enum Choice {
+        #[gin(id = 6, utf8 = "lossy")]
+        Text(String),
+    }
+
+    #[derive(Debug, PartialEq, gin_tonic_derive::Message)]
+    #[gin(root = "crate")]
+    struct Test {
+        #[gin(id = 1, utf8 = "strict")]
+        strict: Option<String>,
+        #[gin(id = 2, utf8 = "lossy")]
+        lossy: String,
+        #[gin(id = 3, utf8 = "lossy")]
+        list: Vec<String>,
+        #[gin(id = 4, scalar = "string", utf8 = "none")]
+        raw: Option<Vec<u8>>,
+        #[gin(id = 5, key_scalar = "uint32", utf8 = "lossy")]
+        map: BTreeMap<u32, String>,
+        #[gin(id = 0, oneof)]
+        choice: Choice,
+    }
+
+    #[test]
+    fn modes() {
+        let buffer =
+            b"\x12\x02a\xff\x1a\x01\xfe\x22\x02b\xff\x2a\x05\x08\x01\x12\x01\xff\x32\x01\xff";
+
+        let read = Test::decode_message(&mut Decoder::new(buffer)).unwrap();
+        assert_eq!(
+            read,
+            Test {
+                strict: None,
+                lossy: String::from("a\u{fffd}"),
+                list: vec![String::from("\u{fffd}")],
+                raw: Some(b"b\xff".to_vec()),
+                map: BTreeMap::from([(1, String::from("\u{fffd}"))]),
+                choice: Choice::Text(String::from("\u{fffd}")),
+            }
+        );
+
+        let err = Test::decode_message(&mut Decoder::new(b"\x0a\x01\xff")).unwrap_err();
+        assert_eq!(err.path(), Some("Test.strict"));
+        assert!(matches!(
+            err,
+            ProtoError::Field { source, .. } if matches!(*source, ProtoError::Utf8(_))
+        ));
+    }
+}
+
+#[cfg(feature = "reflection")]
+mod reflection {
+    use prost_reflect::prost::Message;
+    use prost_reflect::prost_types::{
+        DescriptorProto, FieldDescriptorProto, FileDescriptorProto, FileDescriptorSet,
+        field_descriptor_proto::{Label, Type},
+    };
+
+    use crate::reflection::LazyDescriptorPool;
+
+    #[test]
+    fn lazy_pool() {
+        let set = FileDescriptorSet {
+            file: vec![FileDescriptorProto {
+                name: Some("echo.proto".into()),
+                package: Some("echo.v1".into()),
+                syntax: Some("proto3".into()),
+                message_type: vec![DescriptorProto {
+                    name: Some("Echo".into()),
+                    field: vec![FieldDescriptorProto {
+                        name: Some("text".into()),
+                        number: Some(1),
+                        label: Some(Label::Optional.into()),
+                        r#type: Some(Type::String.into()),
+                        json_name: Some("text".into()),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+        };
+        let bytes: &'static [u8] = Box::leak(set.encode_to_vec().into_boxed_slice());
+
+        let pool = LazyDescriptorPool::new(bytes);
+        assert_eq!(pool.file_descriptor_set(), bytes);
+
+        let echo = pool.message("echo.v1.Echo");
+        assert_eq!(echo.full_name(), "echo.v1.Echo");
+        assert_eq!(
+            echo.fields()
+                .map(|field| field.name().to_owned())
+                .collect::<Vec<_>>(),
+            ["text"]
+        );
+    }
+}
+
+#[cfg(all(feature = "reflection", feature = "tonic"))]
+mod reflection_service {
+    use gin_tonic_core::decoder::Decoder;
+    use prost_reflect::prost::Message as _;
+    use prost_reflect::prost_types::{
+        DescriptorProto, FileDescriptorProto, FileDescriptorSet, MethodDescriptorProto,
+        ServiceDescriptorProto,
+    };
+    use tonic::codegen::tokio_stream::{self, StreamExt};
+
+    use crate::{
+        Message,
+        reflection::{
+            ReflectionService,
+            v1::{
+                ServerReflectionRequest, ServerReflectionResponse,
+                server_reflection_client::ServerReflectionClient,
+                server_reflection_request::MessageRequest,
+                server_reflection_response::MessageResponse,
+            },
+        },
+    };
+
+    fn file_descriptor_set() -> Vec<u8> {
+        let common = FileDescriptorProto {
+            name: Some("common.proto".into()),
+            package: Some("common.v1".into()),
+            syntax: Some("proto3".into()),
+            message_type: vec![DescriptorProto {
+                name: Some("Empty".into()),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let echo = FileDescriptorProto {
+            name: Some("echo.proto".into()),
+            package: Some("echo.v1".into()),
+            syntax: Some("proto3".into()),
+            dependency: vec!["common.proto".into()],
+            service: vec![ServiceDescriptorProto {
+                name: Some("Echo".into()),
+                method: vec![MethodDescriptorProto {
+                    name: Some("Ping".into()),
+                    input_type: Some(".common.v1.Empty".into()),
+                    output_type: Some(".common.v1.Empty".into()),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        FileDescriptorSet {
+            file: vec![common, echo],
+        }
+        .encode_to_vec()
+    }
+
+    fn request(message_request: MessageRequest) -> ServerReflectionRequest {
+        ServerReflectionRequest {
+            host: "localhost".into(),
+            message_request,
+        }
+    }
+
+    fn file_names(response: &ServerReflectionResponse) -> Vec<String> {
+        let MessageResponse::FileDescriptorResponse(files) = &response.message_response else {
+            panic!("unexpected response {response:?}");
+        };
+        files
+            .file_descriptor_proto
+            .iter()
+            .map(|file| {
+                FileDescriptorProto::decode(file.as_slice())
+                    .expect("valid file descriptor")
+                    .name
+                    .unwrap_or_default()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn implicit_defaults() {
+        // grpcurl does not send an empty host
+        let request = ServerReflectionRequest::decode_message(&mut Decoder::new(b"\x3a\x01*"))
+            .expect("valid request");
+        assert!(request.host.is_empty());
+        assert!(matches!(
+            request.message_request,
+            MessageRequest::ListServices(pattern) if pattern == "*"
+        ));
+    }
+
+    #[tokio::test]
+    async fn in_process() {
+        let service = ReflectionService::new()
+            .file_descriptor_set(&file_descriptor_set())
+            .expect("valid set");
+        let mut client = ServerReflectionClient::new(service.into_server());
+
+        let requests = tokio_stream::iter([
+            request(MessageRequest::ListServices(String::new())),
+            request(MessageRequest::FileContainingSymbol(
+                "echo.v1.Echo.Ping".into(),
+            )),
+            request(MessageRequest::FileByFilename("common.proto".into())),
+            request(MessageRequest::FileContainingSymbol(
+                "echo.v1.Missing".into(),
+            )),
+        ]);
+        let responses = client
+            .server_reflection_info(requests)
+            .await
+            .expect("reflection response")
+            .into_inner()
+            .collect::<Result<Vec<_>, _>>()
+            .await
+            .expect("reflection responses");
+        assert_eq!(responses.len(), 4);
+        assert!(
+            responses
+                .iter()
+                .all(|response| response.valid_host == "localhost")
+        );
+
+        let MessageResponse::ListServicesResponse(list) = &responses[0].message_response else {
+            panic!("unexpected response {:?}", responses[0]);
+        };
+        let mut services = list
+            .service
+            .iter()
+            .map(|service| service.name.as_str())
+            .collect::<Vec<_>>();
+        services.sort_unstable();
+        assert_eq!(
+            services,
+            ["echo.v1.Echo", "grpc.reflection.v1.ServerReflection"]
+        );
+
+        assert_eq!(file_names(&responses[1]), ["echo.proto", "common.proto"]);
+        assert_eq!(file_names(&responses[2]), ["common.proto"]);
+
+        let MessageResponse::ErrorResponse(error) = &responses[3].message_response else {
+            panic!("unexpected response {:?}", responses[3]);
+        };
+        assert_eq!(error.error_code, tonic::Code::NotFound as i32);
+        assert!(matches!(
+            &responses[3].original_request.message_request,
+            MessageRequest::FileContainingSymbol(symbol) if symbol == "echo.v1.Missing"
+        ));
+    }
+}
+
+#[cfg(feature = "health")]
+mod health {
+    use tonic::codegen::tokio_stream::StreamExt;
+
+    use crate::health::{
+        HealthReporter, HealthService, ServingStatus,
+        v1::{HealthCheckRequest, health_client::HealthClient, health_server::HealthServer},
+    };
+
+    type Server = HealthServer<HealthService>;
+
+    fn request(service: &str) -> HealthCheckRequest {
+        HealthCheckRequest {
+            service: service.into(),
+        }
+    }
+
+    #[tokio::test]
+    async fn check() {
+        let reporter = HealthReporter::new();
+        let mut client = HealthClient::new(reporter.service());
+
+        let response = client.check(request("")).await.expect("server status");
+        assert_eq!(response.into_inner().status, ServingStatus::Serving);
+
+        let status = client.check(request("grpc.health.v1.Health")).await;
+        assert_eq!(
+            status.expect_err("unknown service").code(),
+            tonic::Code::NotFound
+        );
+
+        reporter.set_not_serving::<Server>();
+        let response = client
+            .check(request("grpc.health.v1.Health"))
+            .await
+            .expect("service status");
+        assert_eq!(response.into_inner().status, ServingStatus::NotServing);
+
+        reporter.clear_service_status("grpc.health.v1.Health");
+        assert_eq!(reporter.service_status("grpc.health.v1.Health"), None);
+        let status = client.check(request("grpc.health.v1.Health")).await;
+        assert_eq!(
+            status.expect_err("cleared service").code(),
+            tonic::Code::NotFound
+        );
+    }
+
+    #[tokio::test]
+    async fn watch() {
+        let reporter = HealthReporter::new();
+        let mut client = HealthClient::new(reporter.service());
+
+        reporter.set_serving::<Server>();
+        let mut statuses = client
+            .watch(request("grpc.health.v1.Health"))
+            .await
+            .expect("watch")
+            .into_inner()
+            .map(|response| response.expect("status").status);
+        assert_eq!(statuses.next().await, Some(ServingStatus::Serving));
+
+        reporter.set_serving::<Server>();
+        reporter.set_not_serving::<Server>();
+        assert_eq!(statuses.next().await, Some(ServingStatus::NotServing));
+
+        reporter.clear_service_status("grpc.health.v1.Health");
+        assert_eq!(statuses.next().await, Some(ServingStatus::ServiceUnknown));
+    }
+
+    #[tokio::test]
+    async fn watch_unknown() {
+        let reporter = HealthReporter::new();
+        let mut client = HealthClient::new(reporter.service());
+
+        let mut statuses = client
+            .watch(request("grpc.health.v1.Health"))
+            .await
+            .expect("watch")
+            .into_inner()
+            .map(|response| response.expect("status").status);
+        assert_eq!(statuses.next().await, Some(ServingStatus::ServiceUnknown));
+
+        // other services don't end or repeat the watch
+        reporter.set_service_status("other.Service", ServingStatus::Serving);
+        reporter.set_not_serving::<Server>();
+        assert_eq!(statuses.next().await, Some(ServingStatus::NotServing));
+
+        reporter.set_serving::<Server>();
+        assert_eq!(statuses.next().await, Some(ServingStatus::Serving));
+    }
+}
+
+#[cfg(feature = "reflection")]
+mod dynamic {
+    use std::collections::{BTreeMap, HashMap};
+
+    use gin_tonic_core::{
+        Message,
+        decoder::{DecodeOptions, Decoder},
+    };
+    use prost_reflect::{
+        DescriptorPool,
+        prost_types::{
+            DescriptorProto, FieldDescriptorProto, FieldOptions, FileDescriptorProto,
+            MessageOptions, OneofDescriptorProto,
+            field_descriptor_proto::{Label, Type},
+        },
+    };
+
+    use crate::{
+        ProtoError,
+        reflection::{DynamicMessage, MapKey, MessageDescriptor, Value},
+    };
+
+    #[derive(Debug, PartialEq, gin_tonic_derive::Message)]
+    #[gin(root = "crate")]
+    struct Inner {
+        #[gin(id = 1, scalar = "int32")]
+        id: i32,
+    }
+
+    #[derive(Debug, PartialEq, gin_tonic_derive::OneOf)]
+    #[gin(root = "crate")]
+    enum Choice {
+        #[gin(id = 4)]
+        Text(String),
+        #[gin(id = 5)]
+        Inner(Inner),
+    }
+
+    #[derive(Debug, PartialEq, gin_tonic_derive::Message)]
+    #[gin(root = "crate")]
+    struct Sample {
+        #[gin(id = 1)]
+        name: String,
+        #[gin(id = 2, scalar = "sint64")]
+        offsets: Vec<i64>,
+        #[gin(id = 3, key_scalar = "string", value_scalar = "int32")]
+        counts: HashMap<String, i32>,
+        #[gin(id = 0, oneof)]
+        choice: Choice,
+        #[gin(id = 6)]
+        nested: Option<Inner>,
+    }
+
+    fn field(name: &str, number: i32, label: Label, ty: Type) -> FieldDescriptorProto {
+        FieldDescriptorProto {
+            name: Some(name.into()),
+            number: Some(number),
+            label: Some(label.into()),
+            r#type: Some(ty.into()),
+            json_name: Some(name.into()),
+            ..Default::default()
+        }
+    }
+
+    fn message_field(name: &str, number: i32, label: Label, ty: &str) -> FieldDescriptorProto {
+        FieldDescriptorProto {
+            type_name: Some(ty.into()),
+            ..field(name, number, label, Type::Message)
+        }
+    }
+
+    fn descriptor() -> MessageDescriptor {
+        let file = FileDescriptorProto {
+            name: Some("sample.proto".into()),
+            package: Some("sample".into()),
+            syntax: Some("proto3".into()),
+            message_type: vec![
+                DescriptorProto {
+                    name: Some("Sample".into()),
+                    field: vec![
+                        field("name", 1, Label::Optional, Type::String),
+                        field("offsets", 2, Label::Repeated, Type::Sint64),
+                        message_field("counts", 3, Label::Repeated, ".sample.Sample.CountsEntry"),
+                        FieldDescriptorProto {
+                            oneof_index: Some(0),
+                            ..field("text", 4, Label::Optional, Type::String)
+                        },
+                        FieldDescriptorProto {
+                            oneof_index: Some(0),
+                            ..message_field("inner", 5, Label::Optional, ".sample.Inner")
+                        },
+                        message_field("nested", 6, Label::Optional, ".sample.Inner"),
+                    ],
+                    nested_type: vec![DescriptorProto {
+                        name: Some("CountsEntry".into()),
+                        field: vec![
+                            field("key", 1, Label::Optional, Type::String),
+                            field("value", 2, Label::Optional, Type::Int32),
+                        ],
+                        options: Some(MessageOptions {
+                            map_entry: Some(true),
+                            ..Default::default()
+                        }),
+                        ..Default::default()
+                    }],
+                    oneof_decl: vec![OneofDescriptorProto {
+                        name: Some("choice".into()),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+                DescriptorProto {
+                    name: Some("Inner".into()),
+                    field: vec![field("id", 1, Label::Optional, Type::Int32)],
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+
+        let mut pool = DescriptorPool::new();
+        pool.add_file_descriptor_proto(file).expect("valid file");
+        pool.get_message_by_name("sample.Sample")
+            .expect("sample message")
+    }
+
+    fn sample() -> Sample {
+        Sample {
+            name: "gin".into(),
+            offsets: vec![-1, 300],
+            counts: HashMap::from([("a".into(), 1), ("b".into(), -2)]),
+            choice: Choice::Inner(Inner { id: 7 }),
+            nested: Some(Inner { id: 3 }),
+        }
+    }
+
+    #[test]
+    fn static_round_trip() {
+        let message = DynamicMessage::from_message(descriptor(), &sample()).expect("decode");
+
+        assert_eq!(message.get("name"), Some(&Value::String("gin".into())));
+        assert_eq!(
+            message.get("offsets"),
+            Some(&Value::List(vec![Value::I64(-1), Value::I64(300)]))
+        );
+        assert_eq!(
+            message.get("counts"),
+            Some(&Value::Map(BTreeMap::from([
+                (MapKey::String("a".into()), Value::I32(1)),
+                (MapKey::String("b".into()), Value::I32(-2)),
+            ])))
+        );
+        assert_eq!(message.get("text"), None);
+        let Some(Value::Message(inner)) = message.get("inner") else {
+            panic!("inner is set");
+        };
+        assert_eq!(inner.get("id"), Some(&Value::I32(7)));
+        assert!(message.unknown_fields().is_empty());
+
+        assert_eq!(message.to_message::<Sample>().expect("transcode"), sample());
+    }
+
+    #[test]
+    fn set() {
+        let descriptor = descriptor();
+        let mut message = DynamicMessage::new(descriptor.clone());
+        message
+            .set("name", Value::String("gin".into()))
+            .expect("valid value");
+        message
+            .set("text", Value::String("hello".into()))
+            .expect("valid value");
+        message
+            .set("offsets", Value::List(vec![Value::I64(4)]))
+            .expect("valid value");
+
+        let inner = descriptor
+            .parent_pool()
+            .get_message_by_name("sample.Inner")
+            .expect("inner message");
+        let mut inner = DynamicMessage::new(inner);
+        inner.set("id", Value::I32(9)).expect("valid value");
+        message
+            .set("nested", Value::Message(inner))
+            .expect("valid value");
+
+        assert!(matches!(
+            message.set("name", Value::I32(1)),
+            Err(ProtoError::Custom(_))
+        ));
+        assert!(matches!(
+            message.set("missing", Value::I32(1)),
+            Err(ProtoError::Custom(_))
+        ));
+
+        let decoded = message.to_message::<Sample>().expect("transcode");
+        assert_eq!(decoded.choice, Choice::Text("hello".into()));
+        assert_eq!(decoded.offsets, [4]);
+        assert_eq!(decoded.nested, Some(Inner { id: 9 }));
+
+        // setting another oneof field replaces the current one
+        let inner = match message.get("nested") {
+            Some(value) => value.clone(),
+            None => panic!("nested is set"),
+        };
+        message.set("inner", inner).expect("valid value");
+        assert_eq!(message.get("text"), None);
+        assert!(message.clear("inner").is_some());
+        assert_eq!(message.fields().count(), 3);
+    }
+
+    #[test]
+    fn unknown_fields() {
+        // field 9 is unknown and field 1 has a varint instead of a string
+        let buffer = b"\x48\x05\x08\x01\x0a\x03gin";
+        let message =
+            DynamicMessage::decode(descriptor(), &mut Decoder::new(buffer)).expect("decode");
+        assert_eq!(message.get("name"), Some(&Value::String("gin".into())));
+        assert!(!message.unknown_fields().is_empty());
+
+        // known fields come first, unknown fields are kept
+        assert_eq!(message.encode_to_vec(), b"\x0a\x03gin\x08\x01\x48\x05");
+    }
+
+    #[test]
+    fn field_path() {
+        let err = DynamicMessage::decode(descriptor(), &mut Decoder::new(b"\x1a\x03\x0a\x01\xff"))
+            .expect_err("invalid utf8");
+        assert_eq!(err.path(), Some("Sample.counts.key"));
+        assert!(matches!(
+            err,
+            ProtoError::Field { source, .. } if matches!(*source, ProtoError::Utf8(_))
+        ));
+
+        let err = DynamicMessage::decode(descriptor(), &mut Decoder::new(b"\x32\x01\x08"))
+            .expect_err("truncated");
+        assert_eq!(err.path(), Some("Sample.nested.id"));
+    }
+
+    #[derive(Debug, PartialEq, gin_tonic_derive::Message)]
+    #[gin(root = "crate")]
+    struct Data {
+        #[gin(id = 2, scalar = "int32")]
+        x: i32,
+    }
+
+    #[derive(Debug, PartialEq, gin_tonic_derive::Message)]
+    #[gin(root = "crate")]
+    struct Grouped {
+        #[gin(id = 1, group)]
+        data: Option<Data>,
+    }
+
+    #[test]
+    fn groups() {
+        let file = FileDescriptorProto {
+            name: Some("grouped.proto".into()),
+            package: Some("sample".into()),
+            message_type: vec![DescriptorProto {
+                name: Some("Grouped".into()),
+                field: vec![FieldDescriptorProto {
+                    type_name: Some(".sample.Grouped.Data".into()),
+                    ..field("data", 1, Label::Optional, Type::Group)
+                }],
+                nested_type: vec![DescriptorProto {
+                    name: Some("Data".into()),
+                    field: vec![field("x", 2, Label::Optional, Type::Int32)],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let mut pool = DescriptorPool::new();
+        pool.add_file_descriptor_proto(file).expect("valid file");
+        let descriptor = pool
+            .get_message_by_name("sample.Grouped")
+            .expect("grouped message");
+
+        let grouped = Grouped {
+            data: Some(Data { x: 5 }),
+        };
+        let message = DynamicMessage::from_message(descriptor, &grouped).expect("decode");
+        let Some(Value::Message(data)) = message.get("data") else {
+            panic!("data is set");
+        };
+        assert_eq!(data.get("x"), Some(&Value::I32(5)));
+        assert_eq!(message.encode_to_vec(), b"\x0b\x10\x05\x0c");
+        assert_eq!(message.to_message::<Grouped>().expect("transcode"), grouped);
+    }
+
+    #[derive(Debug, PartialEq, gin_tonic_derive::Message)]
+    #[gin(root = "crate")]
+    struct Packing {
+        #[gin(id = 1, scalar = "int32")]
+        packed: Vec<i32>,
+        #[gin(id = 2, scalar = "int32", packed = false)]
+        unpacked: Vec<i32>,
+    }
+
+    #[test]
+    fn proto2_packed() {
+        // syntax = "proto2"; message Packing { repeated int32 packed = 1 [packed = true]; repeated int32 unpacked = 2; }
+        let file = FileDescriptorProto {
+            name: Some("packing.proto".into()),
+            package: Some("sample".into()),
+            syntax: Some("proto2".into()),
+            message_type: vec![DescriptorProto {
+                name: Some("Packing".into()),
+                field: vec![
+                    FieldDescriptorProto {
+                        options: Some(FieldOptions {
+                            packed: Some(true),
+                            ..Default::default()
+                        }),
+                        ..field("packed", 1, Label::Repeated, Type::Int32)
+                    },
+                    field("unpacked", 2, Label::Repeated, Type::Int32),
+                ],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let mut pool = DescriptorPool::new();
+        pool.add_file_descriptor_proto(file).expect("valid file");
+        let descriptor = pool
+            .get_message_by_name("sample.Packing")
+            .expect("packing message");
+
+        let packing = Packing {
+            packed: vec![1, 2],
+            unpacked: vec![3, 4],
+        };
+        let message = DynamicMessage::from_message(descriptor, &packing).expect("decode");
+        assert_eq!(message.encode_to_vec(), packing.encode_deterministic());
+        assert_eq!(message.encode_to_vec(), b"\x0a\x02\x01\x02\x10\x03\x10\x04");
+        assert_eq!(message.to_message::<Packing>().expect("transcode"), packing);
+    }
+
+    #[test]
+    fn nesting_limit() {
+        // message Node { Node child = 1; group Node deep = 2; }
+        let file = FileDescriptorProto {
+            name: Some("node.proto".into()),
+            package: Some("sample".into()),
+            message_type: vec![DescriptorProto {
+                name: Some("Node".into()),
+                field: vec![
+                    message_field("child", 1, Label::Optional, ".sample.Node"),
+                    FieldDescriptorProto {
+                        type_name: Some(".sample.Node".into()),
+                        ..field("deep", 2, Label::Optional, Type::Group)
+                    },
+                ],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let mut pool = DescriptorPool::new();
+        pool.add_file_descriptor_proto(file).expect("valid file");
+        let descriptor = pool.get_message_by_name("sample.Node").expect("node");
+
+        let children = |depth: usize| {
+            (0..depth).fold(Vec::new(), |inner, _| {
+                let mut buffer = vec![0x0a, u8::try_from(inner.len()).expect("short")];
+                buffer.extend(inner);
+                buffer
+            })
+        };
+        let decode = |buffer: &[u8], options: DecodeOptions| {
+            DynamicMessage::decode(
+                descriptor.clone(),
+                &mut Decoder::with_options(buffer, options)?,
+            )
+        };
+
+        assert!(decode(&children(2), DecodeOptions::new().max_depth(2)).is_ok());
+        let err = decode(&children(3), DecodeOptions::new().max_depth(2)).unwrap_err();
+        assert_eq!(err.path(), Some("Node.child.child.child"));
+        assert!(matches!(
+            err,
+            ProtoError::Field { source, .. } if matches!(*source, ProtoError::RecursionLimitExceeded(2))
+        ));
+
+        // groups nested deep enough to overflow the stack without a limit
+        let mut buffer = vec![0x13; 200_000];
+        buffer.extend(vec![0x14; 200_000]);
+        let err = decode(&buffer, DecodeOptions::new()).unwrap_err();
+        assert!(matches!(
+            err,
+            ProtoError::Field { source, .. } if matches!(*source, ProtoError::RecursionLimitExceeded(100))
+        ));
+    }
+
+    #[cfg(all(feature = "derive", feature = "tonic"))]
+    #[test]
+    fn generated() {
+        use crate::reflection::v1::{
+            FILE_DESCRIPTOR_SET, FileDescriptorResponse, ServerReflectionRequest,
+            ServerReflectionResponse, server_reflection_request::MessageRequest,
+            server_reflection_response::MessageResponse,
+        };
+
+        let response = ServerReflectionResponse {
+            valid_host: "localhost".into(),
+            original_request: ServerReflectionRequest {
+                host: "localhost".into(),
+                message_request: MessageRequest::FileByFilename("echo.proto".into()),
+            },
+            message_response: MessageResponse::FileDescriptorResponse(FileDescriptorResponse {
+                file_descriptor_proto: vec![b"first".to_vec(), b"second".to_vec()],
+            }),
+        };
+        let mut buffer = vec![0u8; response.message_size_hint()];
+        response.encode_message(&mut gin_tonic_core::encoder::Encoder::new(&mut buffer));
+
+        let pool = DescriptorPool::decode(FILE_DESCRIPTOR_SET).expect("valid set");
+        let descriptor = pool
+            .get_message_by_name("grpc.reflection.v1.ServerReflectionResponse")
+            .expect("response message");
+        let message =
+            DynamicMessage::decode(descriptor, &mut Decoder::new(&buffer)).expect("decode");
+
+        let Some(Value::Message(request)) = message.get("original_request") else {
+            panic!("original request is set");
+        };
+        assert_eq!(
+            request.get("file_by_filename"),
+            Some(&Value::String("echo.proto".into()))
+        );
+        let Some(Value::Message(files)) = message.get("file_descriptor_response") else {
+            panic!("file descriptor response is set");
+        };
+        assert_eq!(
+            files.get("file_descriptor_proto"),
+            Some(&Value::List(vec![
+                Value::Bytes(b"first".to_vec()),
+                Value::Bytes(b"second".to_vec())
+            ]))
+        );
+
+        assert_eq!(message.encode_to_vec(), buffer);
+        let decoded = message
+            .to_message::<ServerReflectionResponse>()
+            .expect("transcode");
+        assert_eq!(decoded.valid_host, "localhost");
+    }
+}
+
+#[cfg(feature = "generator")]
+mod generator {
+    use std::path::PathBuf;
+
+    use protox::prost_reflect::DescriptorPool;
+
+    use crate::{CompileConfig, MapType, Utf8Validation};
+
+    const PROTO: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/proto");
+
+    /// generated files in a temporary directory, removed on drop
+    struct Generated(PathBuf);
+
+    impl Generated {
+        fn compile(name: &str, config: CompileConfig) -> Self {
+            let target = std::env::temp_dir()
+                .join(format!("gin-tonic-generator-{name}-{}", std::process::id()));
+            let _ = std::fs::remove_dir_all(&target);
+            config.include(PROTO).compile_into(&target).unwrap();
+            Self(target)
+        }
+
+        /// content of a generated file, without whitespace to not depend on formatting
+        fn file(&self, path: &str) -> String {
+            std::fs::read_to_string(self.0.join(path))
+                .unwrap()
+                .chars()
+                .filter(|c| !c.is_whitespace())
+                .collect()
+        }
+
+        /// the decoded `FILE_DESCRIPTOR_SET` of a generated file
+        fn descriptor_pool(&self, path: &str) -> DescriptorPool {
+            let content = std::fs::read_to_string(self.0.join(path)).unwrap();
+            let file = syn::parse_file(&content).unwrap();
+            let set = file
+                .items
+                .iter()
+                .find_map(|item| match item {
+                    syn::Item::Const(item) if item.ident == "FILE_DESCRIPTOR_SET" => {
+                        match item.expr.as_ref() {
+                            syn::Expr::Lit(syn::ExprLit {
+                                lit: syn::Lit::ByteStr(bytes),
+                                ..
+                            }) => Some(bytes.value()),
+                            _ => None,
+                        }
+                    }
+                    _ => None,
+                })
+                .unwrap();
+            DescriptorPool::decode(set.as_slice()).unwrap()
+        }
+
+        /// the generated module and its child modules match the files checked in at `source`,
+        /// which are not formatted by rustfmt. `GIN_TONIC_REGENERATE=1` overwrites them
+        fn assert_checked_in(&self, module: &str, source: &str) {
+            let source = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(source);
+            let mut files = vec![(
+                self.0.join(format!("{module}.rs")),
+                source.with_extension("rs"),
+            )];
+            for entry in std::fs::read_dir(self.0.join(module)).into_iter().flatten() {
+                let entry = entry.unwrap();
+                files.push((entry.path(), source.join(entry.file_name())));
+            }
+
+            for (generated, checked_in) in files {
+                if std::env::var_os("GIN_TONIC_REGENERATE").is_some() {
+                    std::fs::create_dir_all(checked_in.parent().unwrap()).unwrap();
+                    std::fs::copy(&generated, &checked_in).unwrap();
+                }
+                assert!(
+                    std::fs::read_to_string(&generated).unwrap()
+                        == std::fs::read_to_string(&checked_in).unwrap_or_default(),
+                    "{} is out of date, regenerate it with `GIN_TONIC_REGENERATE=1 cargo test`",
+                    checked_in.display()
+                );
+            }
+        }
+    }
+
+    impl Drop for Generated {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn assert_contains(content: &str, expected: &str) {
+        let expected = expected
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect::<String>();
+        assert!(
+            content.contains(&expected),
+            "`{expected}` not found in:\n{content}"
+        );
+    }
+
+    #[test]
+    fn proto2() {
+        let generated = Generated::compile(
+            "proto2",
+            CompileConfig::new().add_proto_file(format!("{PROTO}/test/proto2.proto")),
+        );
+        let package = generated.file("gin/test/proto2.rs");
+
+        // defaults
+        assert_contains(
+            &package,
+            r#"#[gin(id = 1u32, scalar = "int32", default = "42i32")] pub count: i32,"#,
+        );
+        assert_contains(
+            &package,
+            r#"#[gin(id = 2u32, scalar = "string", default = ":: std :: string :: String :: from (\"gin\")")]
+            pub name: String,"#,
+        );
+        assert_contains(
+            &package,
+            r#"#[gin(id = 3u32, scalar = "int32", default = "Kind :: B")] pub kind: Kind,"#,
+        );
+
+        // packed
+        assert_contains(
+            &package,
+            r#"#[gin(id = 4u32, scalar = "int32", packed = false)] pub unpacked: Vec<i32>,"#,
+        );
+        assert_contains(
+            &package,
+            r#"#[gin(id = 5u32, scalar = "int32")] pub packed: Vec<i32>,"#,
+        );
+
+        // groups
+        assert_contains(
+            &package,
+            r#"#[gin(id = 6u32, group)] pub inner: Option<defaults::Inner>,"#,
+        );
+        assert_contains(
+            &generated.file("gin/test/proto2/defaults.rs"),
+            r#"pub struct Inner { #[gin(id = 7u32, scalar = "int32")] pub value: Option<i32>, }"#,
+        );
+
+        // extensions
+        assert_contains(
+            &package,
+            r#"#[gin(extensions)] pub extensions: ::gin_tonic::ExtensionSet,"#,
+        );
+        assert_contains(
+            &package,
+            r#"pub const EXTRA: ::gin_tonic::Extension<::gin_tonic::scalars::Int32, i32> =
+            ::gin_tonic::Extension::new(100u32,);"#,
+        );
+
+        assert!(!package.contains("FILE_DESCRIPTOR_SET"));
+        assert!(!package.contains("reflection"));
+    }
+
+    #[test]
+    fn options() {
+        let generated = Generated::compile(
+            "options",
+            CompileConfig::new()
+                .map_type(MapType::BTreeMap, ".gin.test.options.Options.ordered")
+                .bytes(".gin.test.options.Options.payload")
+                .utf8(Utf8Validation::Lossy, ".gin.test.options.Options.name")
+                .add_proto_file(format!("{PROTO}/test/options.proto")),
+        );
+        let package = generated.file("gin/test/options.rs");
+
+        // map_type
+        assert_contains(
+            &package,
+            r#"#[gin(id = 1u32, key_scalar = "string", value_scalar = "int32")]
+            pub counts: ::gin_tonic::fxhash::FxHashMap<String, i32>,"#,
+        );
+        assert_contains(
+            &package,
+            r#"#[gin(id = 2u32, key_scalar = "string", value_scalar = "int32")]
+            pub ordered: ::std::collections::BTreeMap<String, i32>,"#,
+        );
+        // bytes
+        assert_contains(
+            &package,
+            r#"#[gin(id = 3u32, scalar = "bytes")] pub payload: ::gin_tonic::bytes::Bytes,"#,
+        );
+        // utf8
+        assert_contains(
+            &package,
+            r#"#[gin(id = 4u32, scalar = "string", utf8 = "lossy")] pub name: String,"#,
+        );
+        assert_contains(
+            &generated.file("gin/test/options/options.rs"),
+            r#"#[gin(id = 1u32, scalar = "string")] pub label: String,"#,
+        );
+    }
+
+    #[test]
+    fn file_descriptor_set() {
+        let generated = Generated::compile(
+            "file_descriptor_set",
+            CompileConfig::new()
+                .file_descriptor_set()
+                .add_proto_file(format!("{PROTO}/test/proto2.proto"))
+                .add_proto_file(format!("{PROTO}/test/options.proto")),
+        );
+
+        let pool = generated.descriptor_pool("gin/test/proto2.rs");
+        assert!(
+            pool.get_message_by_name("gin.test.proto2.Defaults")
+                .is_some()
+        );
+        assert!(
+            pool.get_extension_by_name("gin.test.proto2.extra")
+                .is_some()
+        );
+        // only the package and its dependencies are embedded
+        assert!(
+            pool.get_message_by_name("gin.test.options.Options")
+                .is_none()
+        );
+
+        let pool = generated.descriptor_pool("gin/test/options.rs");
+        assert!(pool.get_service_by_name("gin.test.options.Echo").is_some());
+
+        let package = generated.file("gin/test/proto2.rs");
+        assert!(!package.contains("DESCRIPTOR_POOL"));
+        assert!(!package.contains("reflection"));
+    }
+
+    #[test]
+    fn reflection() {
+        let generated = Generated::compile(
+            "reflection",
+            CompileConfig::new()
+                .reflection()
+                .add_proto_file(format!("{PROTO}/test/proto2.proto")),
+        );
+        let package = generated.file("gin/test/proto2.rs");
+
+        assert!(package.contains("pubconstFILE_DESCRIPTOR_SET:&[u8]="));
+        assert_contains(
+            &package,
+            r#"pub static DESCRIPTOR_POOL: ::gin_tonic::reflection::LazyDescriptorPool =
+            ::gin_tonic::reflection::LazyDescriptorPool::new(FILE_DESCRIPTOR_SET,);"#,
+        );
+        assert_contains(
+            &package,
+            r#"impl ::gin_tonic::reflection::Descriptor for Defaults {
+                const FULL_NAME: &'static str = "gin.test.proto2.Defaults";
+                fn descriptor() -> ::gin_tonic::reflection::MessageDescriptor {
+                    DESCRIPTOR_POOL.message(Self::FULL_NAME)
+                }
+            }"#,
+        );
+        // nested messages refer to the pool of the package module
+        assert_contains(
+            &generated.file("gin/test/proto2/defaults.rs"),
+            r#"super::DESCRIPTOR_POOL.message(Self::FULL_NAME)"#,
+        );
+    }
+
+    #[test]
+    fn package_less() {
+        let generated = Generated::compile(
+            "package_less",
+            CompileConfig::new()
+                .reflection()
+                .add_proto_file(format!("{PROTO}/test/no_package.proto")),
+        );
+        // package-less types are written to the module with an empty name
+        let root = generated.file(".rs");
+
+        assert_contains(
+            &root,
+            r#"pub static DESCRIPTOR_POOL: ::gin_tonic::reflection::LazyDescriptorPool =
+            ::gin_tonic::reflection::LazyDescriptorPool::new(FILE_DESCRIPTOR_SET,);"#,
+        );
+        assert_contains(
+            &root,
+            r#"const FULL_NAME: &'static str = "Bare";
+            fn descriptor() -> ::gin_tonic::reflection::MessageDescriptor {
+                DESCRIPTOR_POOL.message(Self::FULL_NAME)
+            }"#,
+        );
+        assert_contains(&root, "#[gin(id = 2u32)] pub inner: bare::Inner,");
+        assert_contains(
+            &root,
+            r#"#[gin(id = 3u32, scalar = "int32")] pub kind: Kind,"#,
+        );
+        assert_contains(&root, r#"#[gin(id = 1i32, name = "KIND_A")] A,"#);
+
+        let nested = generated.file("bare.rs");
+        assert_contains(
+            &nested,
+            r#"#[gin(id = 2u32, scalar = "int32")] pub kind: super::Kind,"#,
+        );
+        assert_contains(&nested, "super::DESCRIPTOR_POOL.message(Self::FULL_NAME)");
+
+        let pool = generated.descriptor_pool(".rs");
+        assert!(pool.get_message_by_name("Bare.Inner").is_some());
+    }
+
+    #[cfg(feature = "tonic")]
+    #[test]
+    fn reflection_service() {
+        let generated = Generated::compile(
+            "reflection-service",
+            CompileConfig::new()
+                .root("crate")
+                .zero_defaults(".")
+                .file_descriptor_set()
+                .add_proto_file(format!("{PROTO}/grpc/reflection/v1/reflection.proto")),
+        );
+        generated.assert_checked_in("grpc/reflection/v1", "src/reflection/v1");
+    }
+
+    #[cfg(feature = "tonic")]
+    #[test]
+    fn health_service() {
+        let generated = Generated::compile(
+            "health-service",
+            CompileConfig::new()
+                .root("crate")
+                .zero_defaults(".")
+                .file_descriptor_set()
+                .add_proto_file(format!("{PROTO}/grpc/health/v1/health.proto")),
+        );
+        generated.assert_checked_in("grpc/health/v1", "src/health/v1");
+    }
+
+    #[test]
+    fn edition() {
+        let target = std::env::temp_dir().join(format!(
+            "gin-tonic-generator-edition-{}",
+            std::process::id()
+        ));
+        let err = CompileConfig::new()
+            .include(PROTO)
+            .add_proto_file(format!("{PROTO}/test/edition.proto"))
+            .compile_into(&target)
+            .unwrap_err();
+        let _ = std::fs::remove_dir_all(&target);
+
+        assert_eq!(
+            err.to_string(),
+            r#"test/edition.proto: protobuf editions are not supported, declare `syntax = "proto2"` or `syntax = "proto3"` instead"#
+        );
+    }
+}